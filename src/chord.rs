@@ -1,17 +1,33 @@
 use std::fmt;
 use std::convert::TryFrom;
+use std::str::FromStr;
 
-use crate::{Note, Interval};
+use crate::{Note, Interval, IntervalQuality, Error, SpellingPolicy};
+use crate::note::parse_note_prefix;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ChordPosition {
     Root,
     FirstInversion,
     SecondInversion,
-    ThirdInversion
+    ThirdInversion,
+    FourthInversion,
+    FifthInversion,
+    SixthInversion
 }
 
-#[derive(Debug, Copy, Clone)]
+/// Chord positions indexed by the chord tone found in the bass
+const CHORD_POSITIONS : [ChordPosition; 7] = [
+    ChordPosition::Root,
+    ChordPosition::FirstInversion,
+    ChordPosition::SecondInversion,
+    ChordPosition::ThirdInversion,
+    ChordPosition::FourthInversion,
+    ChordPosition::FifthInversion,
+    ChordPosition::SixthInversion
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ChordQuality{
     Major,
     Minor,
@@ -21,6 +37,7 @@ pub enum ChordQuality{
     SuspendedFour,
     FlatFive,
     SuspendedTwoFlatFive,
+    Power,
     DominantSeventh,
     MajorSeventh,
     MinorSeventh,
@@ -31,13 +48,283 @@ pub enum ChordQuality{
     DominantSeventhFlatFive,
     MinorMajorSeventh,
     AugmentedMajorSeventh,
-    SuspendedTwoSuspendedFour
+    SuspendedTwoSuspendedFour,
+    DominantNinth,
+    MajorNinth,
+    MinorNinth,
+    DominantEleventh,
+    MajorEleventh,
+    MinorEleventh,
+    DominantThirteenth,
+    MajorThirteenth,
+    MinorThirteenth
+}
+
+/// A change made to the tones of a chord quality e.g. the b9 of C7(b9) or the 6 of C6
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChordModifier {
+    /// Adds a tone to the chord
+    Add(Interval),
+    /// Replaces the tone of the same number in the chord, or adds it when missing
+    Alter(Interval),
+    /// Removes the tone of the given number from the chord
+    Omit(u32),
+    /// Replaces the third with a second or a fourth e.g. the sus4 of C7sus4
+    Suspend(Interval)
+}
+
+impl fmt::Display for ChordModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add(interval) => write!(f, "add{}", degree_name(interval)),
+            Self::Alter(interval) => write!(f, "{}", degree_name(interval)),
+            Self::Omit(number) => write!(f, "no{}", number),
+            Self::Suspend(interval) => write!(f, "sus{}", interval.get_number())
+        }
+    }
+}
+
+/// Names a chord tone by its number, lowered or raised from the major or perfect interval e.g. b9 or #11
+fn degree_name(interval: &Interval) -> String {
+    let accidental = match interval.get_quality() {
+        IntervalQuality::Major | IntervalQuality::Perfect => String::new(),
+        IntervalQuality::Minor => String::from("b"),
+        IntervalQuality::Augmented(n) => "#".repeat(n as usize),
+        IntervalQuality::Diminished(n) if matches!(interval.get_number() % 7, 1 | 4 | 5) => "b".repeat(n as usize),
+        IntervalQuality::Diminished(n) => "b".repeat(n as usize + 1)
+    };
+    format!("{}{}", accidental, interval.get_number())
+}
+
+/// Parses a chord tone name such as "b9", "#11" or "13" into its interval from the root
+fn degree_from_name(name: &str) -> Option<Interval> {
+    let split_idx = name.find(|c: char| c.is_ascii_digit())?;
+    let (accidentals, number) = name.split_at(split_idx);
+    let number = number.parse::<u32>().ok().filter(|&n| n > 0)?;
+    let perfect = matches!(number % 7, 1 | 4 | 5);
+    let quality = match accidentals {
+        "" if perfect => String::from("P"),
+        "" => String::from("M"),
+        "b" if !perfect => String::from("m"),
+        s if s.chars().all(|c| c == '#') => "A".repeat(s.len()),
+        s if s.chars().all(|c| c == 'b') => "d".repeat(if perfect { s.len() } else { s.len() - 1 }),
+        _ => return None
+    };
+    Interval::from_name( &format!("{}{}", quality, number) ).ok()
+}
+
+/// Modifiers written as "alt" on a dominant seventh chord
+fn altered_modifiers() -> Vec<ChordModifier> {
+    ["d5","A5","m9","A9"].iter()
+        .map(|s| ChordModifier::Alter(Interval::from_name(s).unwrap()) )
+        .collect()
+}
+
+/// Chords with added or altered tones looked for when identifying notes
+fn modified_chord_shapes() -> Vec<(ChordQuality, Vec<ChordModifier>)> {
+    let sixth = ChordModifier::Add(Interval::from_name("M6").unwrap());
+    let ninth = ChordModifier::Add(Interval::from_name("M9").unwrap());
+    let alter = |s: &str| ChordModifier::Alter(Interval::from_name(s).unwrap());
+    vec![
+        (ChordQuality::Major, vec![sixth]),
+        (ChordQuality::Minor, vec![sixth]),
+        (ChordQuality::Major, vec![sixth, ninth]),
+        (ChordQuality::Minor, vec![sixth, ninth]),
+        (ChordQuality::Major, vec![ninth]),
+        (ChordQuality::Minor, vec![ninth]),
+        (ChordQuality::DominantSeventh, vec![alter("m9")]),
+        (ChordQuality::DominantSeventh, vec![alter("A9")]),
+        (ChordQuality::DominantSeventh, vec![alter("A11")]),
+        (ChordQuality::DominantSeventh, vec![alter("m13")]),
+        (ChordQuality::DominantSeventh, vec![alter("m9"), alter("A9")]),
+        (ChordQuality::DominantSeventh, vec![alter("m9"), alter("m13")]),
+        (ChordQuality::DominantSeventh, vec![alter("A9"), alter("m13")]),
+        (ChordQuality::DominantSeventh, altered_modifiers()),
+        (ChordQuality::DominantNinth, vec![alter("A11")]),
+        (ChordQuality::MajorSeventh, vec![alter("A11")]),
+        (ChordQuality::MajorNinth, vec![alter("A11")]),
+        (ChordQuality::DominantThirteenth, vec![alter("m9")]),
+        (ChordQuality::DominantThirteenth, vec![alter("A11")])
+    ]
+}
+
+/// Every chord quality followed by the chords with added or altered tones
+pub(crate) fn all_chord_shapes() -> Vec<(ChordQuality, Vec<ChordModifier>)> {
+    all_chord_qualities().into_iter()
+        .map(|quality| (quality, Vec::new()))
+        .chain(modified_chord_shapes())
+        .collect()
+}
+
+/// Returns every chord quality
+pub fn all_chord_qualities() -> Vec<ChordQuality> {
+    vec![
+        ChordQuality::Major,
+        ChordQuality::Minor,
+        ChordQuality::Diminished,
+        ChordQuality::Augmented,
+        ChordQuality::SuspendedTwo,
+        ChordQuality::SuspendedFour,
+        ChordQuality::FlatFive,
+        ChordQuality::SuspendedTwoFlatFive,
+        ChordQuality::Power,
+        ChordQuality::DominantSeventh,
+        ChordQuality::MajorSeventh,
+        ChordQuality::MinorSeventh,
+        ChordQuality::DiminishedSeventh,
+        ChordQuality::AugmentedSeventh,
+        ChordQuality::MajorSeventhFlatFive,
+        ChordQuality::MinorSeventhFlatFive,
+        ChordQuality::DominantSeventhFlatFive,
+        ChordQuality::MinorMajorSeventh,
+        ChordQuality::AugmentedMajorSeventh,
+        ChordQuality::SuspendedTwoSuspendedFour,
+        ChordQuality::DominantNinth,
+        ChordQuality::MajorNinth,
+        ChordQuality::MinorNinth,
+        ChordQuality::DominantEleventh,
+        ChordQuality::MajorEleventh,
+        ChordQuality::MinorEleventh,
+        ChordQuality::DominantThirteenth,
+        ChordQuality::MajorThirteenth,
+        ChordQuality::MinorThirteenth
+    ]
+}
+
+/// Alternative spellings of chord qualities once normalised, on top of their displayed names
+const QUALITY_ALIASES : [(&str, ChordQuality); 6] = [
+    ("Maj", ChordQuality::Major),
+    ("2", ChordQuality::SuspendedTwo),
+    ("mb5", ChordQuality::Diminished),
+    ("#5", ChordQuality::Augmented),
+    ("7#5", ChordQuality::AugmentedSeventh),
+    ("Maj7#5", ChordQuality::AugmentedMajorSeventh)
+];
+
+/// Rewrites the common notations of a chord symbol suffix into the ones used when displaying chords
+/// e.g. "min7(♭5)" becomes "m7b5" and "Δ" becomes "Maj7"
+fn normalise_chord_suffix(suffix: &str) -> String {
+    let mut res = String::new();
+    let mut rest = suffix.trim();
+    while let Some(c) = rest.chars().next() {
+        let followed_by_digit = |len: usize| rest[len..].starts_with(|c: char| c.is_ascii_digit());
+        let (replacement, len) = if rest.starts_with("Maj") || rest.starts_with("maj") || rest.starts_with("MAJ") {
+            ("Maj", 3)
+        } else if rest.starts_with("min") {
+            ("m", 3)
+        } else if rest.starts_with("mi") && !rest.starts_with("mMaj") {
+            ("m", 2)
+        } else if rest.starts_with("madd") {
+            ("m", 1)
+        } else if rest.starts_with("ma") {
+            ("Maj", 2)
+        } else if rest.starts_with("dim") {
+            ("º", 3)
+        } else if rest.starts_with("aug") {
+            ("+", 3)
+        } else if rest.starts_with("omit") {
+            ("no", 4)
+        } else if rest.starts_with("no") {
+            ("no", 2)
+        } else if rest.starts_with("ø7") {
+            ("m7b5", "ø7".len())
+        } else if rest.starts_with("sus") && !followed_by_digit(3) {
+            ("sus4", 3)
+        } else {
+            match c {
+                'Δ' if followed_by_digit(c.len_utf8()) => ("Maj", c.len_utf8()),
+                'Δ' => ("Maj7", c.len_utf8()),
+                'M' => ("Maj", 1),
+                'ø' => ("m7b5", c.len_utf8()),
+                '°' | 'o' => ("º", c.len_utf8()),
+                '♭' => ("b", c.len_utf8()),
+                '♯' => ("#", c.len_utf8()),
+                '-' | '−' if res.is_empty() => ("m", c.len_utf8()),
+                '-' | '−' => ("b", c.len_utf8()),
+                '+' if !res.is_empty() => ("#", 1),
+                '(' | ')' | ',' | ' ' => ("", 1),
+                _ => (&rest[..c.len_utf8()], c.len_utf8())
+            }
+        };
+        res.push_str(replacement);
+        rest = &rest[len..];
+    }
+    res
+}
+
+/// Parses a chord symbol suffix into the longest matching quality followed by its modifiers
+/// e.g. "7(b9,#11)", "m6/9" or "add9"
+fn parse_chord_suffix(suffix: &str) -> Option<(ChordQuality, Vec<ChordModifier>)> {
+    let normalised = normalise_chord_suffix(suffix);
+    let (quality, name_len) = all_chord_qualities().into_iter()
+        .map(|quality| (quality, quality.to_string().replace(['(', ')'], "")))
+        .chain(QUALITY_ALIASES.iter().map(|alias| (alias.1, alias.0.to_string())))
+        .filter(|(_, name)| normalised.starts_with(name.as_str()))
+        .map(|(quality, name)| (quality, name.len()))
+        .max_by_key(|elt| elt.1)?;
+    let mut rest = &normalised[name_len..];
+    let mut modifiers = Vec::new();
+    if let Some(stripped) = rest.strip_prefix("6/9").or_else(|| rest.strip_prefix("69")) {
+        modifiers.push( ChordModifier::Add(Interval::from_name("M6").unwrap()) );
+        modifiers.push( ChordModifier::Add(Interval::from_name("M9").unwrap()) );
+        rest = stripped;
+    } else if let Some(stripped) = rest.strip_prefix('6') {
+        modifiers.push( ChordModifier::Add(Interval::from_name("M6").unwrap()) );
+        rest = stripped;
+    }
+    while !rest.is_empty() {
+        if let Some(stripped) = rest.strip_prefix("alt") {
+            modifiers.extend( altered_modifiers() );
+            rest = stripped;
+            continue;
+        }
+        if let Some(stripped) = rest.strip_prefix("sus") {
+            let interval = match stripped.chars().next() {
+                Some('2') => "M2",
+                Some('4') => "P4",
+                _ => return None
+            };
+            modifiers.push( ChordModifier::Suspend(Interval::from_name(interval).unwrap()) );
+            rest = &stripped[1..];
+            continue;
+        }
+        let (kind, degree) = if let Some(stripped) = rest.strip_prefix("add") {
+            ("add", stripped)
+        } else if let Some(stripped) = rest.strip_prefix("no") {
+            ("no", stripped)
+        } else {
+            ("", rest)
+        };
+        let digits_idx = degree.find(|c: char| c.is_ascii_digit())?;
+        let end_idx = degree[digits_idx..].find(|c: char| !c.is_ascii_digit()).map(|idx| digits_idx + idx).unwrap_or(degree.len());
+        let name = &degree[..end_idx];
+        let modifier = match kind {
+            "add" => ChordModifier::Add( degree_from_name(name)? ),
+            "no" => ChordModifier::Omit( name.parse().ok()? ),
+            _ if digits_idx > 0 => ChordModifier::Alter( degree_from_name(name)? ),
+            _ => return None
+        };
+        modifiers.push( modifier );
+        rest = &degree[end_idx..];
+    }
+    Some( (quality, modifiers) )
+}
+
+impl FromStr for ChordQuality {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalised = normalise_chord_suffix(s);
+        all_chord_qualities().into_iter()
+            .find(|quality| quality.to_string().replace(['(', ')'], "") == normalised)
+            .or_else(|| QUALITY_ALIASES.iter().find(|alias| alias.0 == normalised).map(|alias| alias.1))
+            .ok_or(Error::UnknownName { kind: "ChordQuality", name: s.to_string() })
+    }
 }
 
 impl TryFrom<&Vec<Interval>> for ChordQuality {
-    type Error = &'static str;
+    type Error = Error;
     fn try_from(value: &Vec<Interval>) -> Result<Self, Self::Error> {
-        let intervals_by_value : Vec<u32> = value.into_iter()
+        let intervals_by_value : Vec<i32> = value.iter()
             .map(|elt| elt.get_value() )
             .collect();
         match intervals_by_value.as_slice() {
@@ -49,6 +336,7 @@ impl TryFrom<&Vec<Interval>> for ChordQuality {
             [5,2,5] => Ok(Self::SuspendedFour),
             [4,2,6] => Ok(Self::FlatFive),
             [2,4,6] => Ok(Self::SuspendedTwoFlatFive),
+            [7,5] => Ok(Self::Power),
             [4,3,3,2] => Ok(Self::DominantSeventh),
             [4,3,4,1] => Ok(Self::MajorSeventh),
             [3,4,3,2] => Ok(Self::MinorSeventh),
@@ -60,11 +348,69 @@ impl TryFrom<&Vec<Interval>> for ChordQuality {
             [3,3,4,2] => Ok(Self::MinorSeventhFlatFive),
             [4,2,4,2] => Ok(Self::DominantSeventhFlatFive),
             [2,3,2,5] => Ok(Self::SuspendedTwoSuspendedFour), 
-            _ => Err("No ChordQuality found for these intervals")
+            // Extended chords are matched against their stacked intervals, closed back to the root
+            _ => all_chord_qualities().into_iter()
+                .filter(|quality| quality.is_extended())
+                .find(|quality| {
+                    let root_values : Vec<i32> = quality.get_intervals().iter().map(|elt| elt.get_value() ).collect();
+                    let closing = (-root_values[root_values.len() - 1]).rem_euclid(12);
+                    let shape : Vec<i32> = root_values.windows(2)
+                        .map(|w| w[1] - w[0] )
+                        .chain(std::iter::once(closing))
+                        .collect();
+                    shape == intervals_by_value
+                })
+                .ok_or_else(|| Error::UnknownChordShape(value.clone()))
         }
     }
 }
 
+impl ChordQuality {
+
+    /// Get the Intervals of the chord tones from the root, each tone keeping its degree e.g. d5 rather than A4
+    pub fn get_intervals(&self) -> Vec<Interval> {
+        let interval_names = match self {
+            Self::Major => vec!["P1","M3","P5"],
+            Self::Minor => vec!["P1","m3","P5"],
+            Self::Diminished => vec!["P1","m3","d5"],
+            Self::Augmented => vec!["P1","M3","A5"],
+            Self::SuspendedTwo => vec!["P1","M2","P5"],
+            Self::SuspendedFour => vec!["P1","P4","P5"],
+            Self::FlatFive => vec!["P1","M3","d5"],
+            Self::SuspendedTwoFlatFive => vec!["P1","M2","d5"],
+            Self::Power => vec!["P1","P5"],
+            Self::DominantSeventh => vec!["P1","M3","P5","m7"],
+            Self::MajorSeventh => vec!["P1","M3","P5","M7"],
+            Self::MinorSeventh => vec!["P1","m3","P5","m7"],
+            Self::DiminishedSeventh => vec!["P1","m3","d5","d7"],
+            Self::AugmentedSeventh => vec!["P1","M3","A5","m7"],
+            Self::MajorSeventhFlatFive => vec!["P1","M3","d5","M7"],
+            Self::MinorSeventhFlatFive => vec!["P1","m3","d5","m7"],
+            Self::DominantSeventhFlatFive => vec!["P1","M3","d5","m7"],
+            Self::MinorMajorSeventh => vec!["P1","m3","P5","M7"],
+            Self::AugmentedMajorSeventh => vec!["P1","M3","A5","M7"],
+            Self::SuspendedTwoSuspendedFour => vec!["P1","M2","P4","P5"],
+            Self::DominantNinth => vec!["P1","M3","P5","m7","M9"],
+            Self::MajorNinth => vec!["P1","M3","P5","M7","M9"],
+            Self::MinorNinth => vec!["P1","m3","P5","m7","M9"],
+            Self::DominantEleventh => vec!["P1","M3","P5","m7","M9","P11"],
+            Self::MajorEleventh => vec!["P1","M3","P5","M7","M9","P11"],
+            Self::MinorEleventh => vec!["P1","m3","P5","m7","M9","P11"],
+            Self::DominantThirteenth => vec!["P1","M3","P5","m7","M9","P11","M13"],
+            Self::MajorThirteenth => vec!["P1","M3","P5","M7","M9","P11","M13"],
+            Self::MinorThirteenth => vec!["P1","m3","P5","m7","M9","P11","M13"]
+        };
+        interval_names.iter()
+            .map(|s| Interval::from_name(s).unwrap() )
+            .collect()
+    }
+
+    /// Checks if the chord stacks thirds beyond the seventh
+    pub fn is_extended(&self) -> bool {
+        self.get_intervals().len() > 4
+    }
+}
+
 impl fmt::Display for ChordQuality {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -76,6 +422,7 @@ impl fmt::Display for ChordQuality {
             Self::SuspendedFour => write!(f, "sus4"),
             Self::FlatFive => write!(f, "(b5)"),
             Self::SuspendedTwoFlatFive => write!(f, "sus2(b5)"),
+            Self::Power => write!(f, "5"),
             Self::DominantSeventh => write!(f, "7"),
             Self::MajorSeventh => write!(f, "Maj7"),
             Self::MinorSeventh => write!(f, "m7"),
@@ -86,7 +433,16 @@ impl fmt::Display for ChordQuality {
             Self::DominantSeventhFlatFive => write!(f, "7(b5)"),
             Self::MinorMajorSeventh => write!(f, "mMaj7"),
             Self::AugmentedMajorSeventh => write!(f, "+Maj7"),
-            Self::SuspendedTwoSuspendedFour => write!(f, "sus2sus4"),   
+            Self::SuspendedTwoSuspendedFour => write!(f, "sus2sus4"),
+            Self::DominantNinth => write!(f, "9"),
+            Self::MajorNinth => write!(f, "Maj9"),
+            Self::MinorNinth => write!(f, "m9"),
+            Self::DominantEleventh => write!(f, "11"),
+            Self::MajorEleventh => write!(f, "Maj11"),
+            Self::MinorEleventh => write!(f, "m11"),
+            Self::DominantThirteenth => write!(f, "13"),
+            Self::MajorThirteenth => write!(f, "Maj13"),
+            Self::MinorThirteenth => write!(f, "m13")
         }
     }
 }
@@ -95,115 +451,221 @@ impl fmt::Display for ChordQuality {
 pub struct Chord{
     root_note: Note,
     quality: ChordQuality,
-    position: ChordPosition
+    modifiers: Vec<ChordModifier>,
+    position: ChordPosition,
+    bass_note: Option<Note>
 }
 
 impl Chord{
 
     /// Creates a Chord from notes
     pub fn new(root_note: Note, quality: ChordQuality, position: ChordPosition) -> Chord {
-        Chord{root_note, quality, position}
+        Chord{root_note, quality, modifiers: Vec::new(), position, bass_note: None}
     }
 
-    pub fn from_intervals(root_note: Note, intervals: &Vec<Interval>) -> Option<Chord> {
-        let intervals_by_value : Vec<u32> = intervals.iter()
+    /// Creates a Chord whose quality has tones added, altered or omitted e.g. C7(b9)
+    pub fn with_modifiers(root_note: Note, quality: ChordQuality, modifiers: &[ChordModifier], position: ChordPosition) -> Chord {
+        Chord{root_note, quality, modifiers: modifiers.to_vec(), position, bass_note: None}
+    }
+
+    /// Returns the chord played over a bass note e.g. C/D. A bass note belonging to the chord
+    /// selects the matching inversion instead
+    pub fn with_bass(&self, bass_note: &Note) -> Chord {
+        let root_position = Chord::with_modifiers( self.root_note, self.quality, &self.modifiers, ChordPosition::Root );
+        match root_position.get_notes().iter().position(|note| note == bass_note) {
+            Some(index) if index < CHORD_POSITIONS.len() => Chord::with_modifiers( self.root_note, self.quality, &self.modifiers, CHORD_POSITIONS[index] ),
+            _ => Chord{ bass_note: Some(*bass_note), ..self.clone() }
+        }
+    }
+
+    /// Creates a Chord from a root note and the intervals between consecutive notes
+    pub fn from_intervals(root_note: Note, intervals: &[Interval]) -> Result<Chord, Error> {
+        let intervals_by_value : Vec<i32> = intervals.iter()
             .map(|elt| elt.get_value() )
             .collect();
         match intervals_by_value.as_slice() {
             // TRIADS
             // Major
-            [4,3] => Some( Chord::new(root_note, ChordQuality::Major, ChordPosition::Root) ),
-            [3,5] => Some( Chord::new(root_note, ChordQuality::Major, ChordPosition::FirstInversion) ),
-            [5,4] => Some( Chord::new(root_note, ChordQuality::Major, ChordPosition::SecondInversion) ),
+            [4,3] => Ok( Chord::new(root_note, ChordQuality::Major, ChordPosition::Root) ),
+            [3,5] => Ok( Chord::new(root_note, ChordQuality::Major, ChordPosition::FirstInversion) ),
+            [5,4] => Ok( Chord::new(root_note, ChordQuality::Major, ChordPosition::SecondInversion) ),
             // Minor
-            [3,4] => Some( Chord::new(root_note, ChordQuality::Minor, ChordPosition::Root) ),
-            [4,5] => Some( Chord::new(root_note, ChordQuality::Minor, ChordPosition::FirstInversion) ),
-            [5,3] => Some( Chord::new(root_note, ChordQuality::Minor, ChordPosition::SecondInversion) ),
+            [3,4] => Ok( Chord::new(root_note, ChordQuality::Minor, ChordPosition::Root) ),
+            [4,5] => Ok( Chord::new(root_note, ChordQuality::Minor, ChordPosition::FirstInversion) ),
+            [5,3] => Ok( Chord::new(root_note, ChordQuality::Minor, ChordPosition::SecondInversion) ),
             // Diminished
-            [3,3] => Some( Chord::new(root_note, ChordQuality::Diminished, ChordPosition::Root) ),
-            [3,6] => Some( Chord::new(root_note, ChordQuality::Diminished, ChordPosition::FirstInversion) ),
-            [6,3] => Some( Chord::new(root_note, ChordQuality::Diminished, ChordPosition::SecondInversion) ),
+            [3,3] => Ok( Chord::new(root_note, ChordQuality::Diminished, ChordPosition::Root) ),
+            [3,6] => Ok( Chord::new(root_note, ChordQuality::Diminished, ChordPosition::FirstInversion) ),
+            [6,3] => Ok( Chord::new(root_note, ChordQuality::Diminished, ChordPosition::SecondInversion) ),
             // Augmented
-            [4,4] => Some( Chord::new(root_note, ChordQuality::Augmented, ChordPosition::Root) ), 
+            [4,4] => Ok( Chord::new(root_note, ChordQuality::Augmented, ChordPosition::Root) ), 
             // Suspended2
-            [2,5] => Some( Chord::new(root_note, ChordQuality::SuspendedTwo, ChordPosition::Root) ),
-            [5,5] => Some( Chord::new(root_note, ChordQuality::SuspendedTwo, ChordPosition::FirstInversion) ),
+            [2,5] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwo, ChordPosition::Root) ),
+            [5,5] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwo, ChordPosition::FirstInversion) ),
             // Suspended4
-            [5,2] => Some( Chord::new(root_note, ChordQuality::SuspendedFour, ChordPosition::Root) ),
+            [5,2] => Ok( Chord::new(root_note, ChordQuality::SuspendedFour, ChordPosition::Root) ),
             // Flat5
-            [4,2] => Some( Chord::new(root_note, ChordQuality::FlatFive, ChordPosition::Root) ),
-            [2,6] => Some( Chord::new(root_note, ChordQuality::FlatFive, ChordPosition::FirstInversion) ),
-            [6,4] => Some( Chord::new(root_note, ChordQuality::FlatFive, ChordPosition::SecondInversion) ),
+            [4,2] => Ok( Chord::new(root_note, ChordQuality::FlatFive, ChordPosition::Root) ),
+            [2,6] => Ok( Chord::new(root_note, ChordQuality::FlatFive, ChordPosition::FirstInversion) ),
+            [6,4] => Ok( Chord::new(root_note, ChordQuality::FlatFive, ChordPosition::SecondInversion) ),
             // supended2Flat5
-            [2,4] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoFlatFive, ChordPosition::Root) ),
-            [4,6] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoFlatFive, ChordPosition::FirstInversion) ),
-            [6,2] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoFlatFive, ChordPosition::SecondInversion) ),
+            [2,4] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoFlatFive, ChordPosition::Root) ),
+            [4,6] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoFlatFive, ChordPosition::FirstInversion) ),
+            [6,2] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoFlatFive, ChordPosition::SecondInversion) ),
+            // Power
+            [7] => Ok( Chord::new(root_note, ChordQuality::Power, ChordPosition::Root) ),
+            [5] => Ok( Chord::new(root_note, ChordQuality::Power, ChordPosition::FirstInversion) ),
             // EXTENDED CHORDS
             // Dominant7
-            [4,3,3] => Some( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::Root) ),
-            [3,3,2] => Some( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::FirstInversion) ),
-            [3,2,4] => Some( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::SecondInversion) ),
-            [2,4,3] => Some( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::ThirdInversion) ),
+            [4,3,3] => Ok( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::Root) ),
+            [3,3,2] => Ok( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::FirstInversion) ),
+            [3,2,4] => Ok( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::SecondInversion) ),
+            [2,4,3] => Ok( Chord::new(root_note, ChordQuality::DominantSeventh, ChordPosition::ThirdInversion) ),
             // Major7
-            [4,3,4] => Some( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::Root) ),
-            [3,4,1] => Some( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::FirstInversion) ),
-            [4,1,4] => Some( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::SecondInversion) ),
-            [1,4,3] => Some( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::ThirdInversion) ),
+            [4,3,4] => Ok( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::Root) ),
+            [3,4,1] => Ok( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::FirstInversion) ),
+            [4,1,4] => Ok( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::SecondInversion) ),
+            [1,4,3] => Ok( Chord::new(root_note, ChordQuality::MajorSeventh, ChordPosition::ThirdInversion) ),
             // Minor7
-            [3,4,3] => Some( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::Root) ),
-            [4,3,2] => Some( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::FirstInversion) ),
-            [3,2,3] => Some( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::SecondInversion) ),
-            [2,3,4] => Some( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::ThirdInversion) ),
+            [3,4,3] => Ok( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::Root) ),
+            [4,3,2] => Ok( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::FirstInversion) ),
+            [3,2,3] => Ok( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::SecondInversion) ),
+            [2,3,4] => Ok( Chord::new(root_note, ChordQuality::MinorSeventh, ChordPosition::ThirdInversion) ),
             // MinorMajor7
-            [3,4,4] => Some( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::Root) ),
-            [4,4,1] => Some( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::FirstInversion) ),
-            [4,1,3] => Some( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::SecondInversion) ),
-            [1,3,4] => Some( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::ThirdInversion) ),
+            [3,4,4] => Ok( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::Root) ),
+            [4,4,1] => Ok( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::FirstInversion) ),
+            [4,1,3] => Ok( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::SecondInversion) ),
+            [1,3,4] => Ok( Chord::new(root_note, ChordQuality::MinorMajorSeventh, ChordPosition::ThirdInversion) ),
             // Diminshed7
-            [3,3,3] => Some( Chord::new(root_note, ChordQuality::DiminishedSeventh, ChordPosition::Root) ),
+            [3,3,3] => Ok( Chord::new(root_note, ChordQuality::DiminishedSeventh, ChordPosition::Root) ),
             // Augmented7
-            [4,4,2] => Some( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::Root) ),
-            [4,2,2] => Some( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::FirstInversion) ),
-            [2,2,4] => Some( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::SecondInversion) ),
-            [2,4,4] => Some( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::ThirdInversion) ),
+            [4,4,2] => Ok( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::Root) ),
+            [4,2,2] => Ok( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::FirstInversion) ),
+            [2,2,4] => Ok( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::SecondInversion) ),
+            [2,4,4] => Ok( Chord::new(root_note, ChordQuality::AugmentedSeventh, ChordPosition::ThirdInversion) ),
             // AugmentedMajor7
-            [4,4,3] => Some( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::Root) ),
-            [4,3,1] => Some( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::FirstInversion) ),
-            [3,1,4] => Some( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::SecondInversion) ),
-            [1,4,4] => Some( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::ThirdInversion) ),
+            [4,4,3] => Ok( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::Root) ),
+            [4,3,1] => Ok( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::FirstInversion) ),
+            [3,1,4] => Ok( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::SecondInversion) ),
+            [1,4,4] => Ok( Chord::new(root_note, ChordQuality::AugmentedMajorSeventh, ChordPosition::ThirdInversion) ),
             // Major7Flat5
-            [4,2,5] => Some( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::Root) ),
-            [2,5,1] => Some( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::FirstInversion) ),
-            [5,1,4] => Some( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::SecondInversion) ),
-            [1,4,2] => Some( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::ThirdInversion) ),
+            [4,2,5] => Ok( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::Root) ),
+            [2,5,1] => Ok( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::FirstInversion) ),
+            [5,1,4] => Ok( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::SecondInversion) ),
+            [1,4,2] => Ok( Chord::new(root_note, ChordQuality::MajorSeventhFlatFive, ChordPosition::ThirdInversion) ),
             // Minor7Flat5
-            [3,3,4] => Some( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::Root) ),
-            [3,4,2] => Some( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::FirstInversion) ),
-            [4,2,3] => Some( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::SecondInversion) ),
-            [2,3,3] => Some( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::ThirdInversion) ),
+            [3,3,4] => Ok( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::Root) ),
+            [3,4,2] => Ok( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::FirstInversion) ),
+            [4,2,3] => Ok( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::SecondInversion) ),
+            [2,3,3] => Ok( Chord::new(root_note, ChordQuality::MinorSeventhFlatFive, ChordPosition::ThirdInversion) ),
             // Dominant7Flat5
-            [4,2,4] => Some( Chord::new(root_note, ChordQuality::DominantSeventhFlatFive, ChordPosition::Root) ),
-            [2,4,2] => Some( Chord::new(root_note, ChordQuality::DominantSeventhFlatFive, ChordPosition::FirstInversion) ),
+            [4,2,4] => Ok( Chord::new(root_note, ChordQuality::DominantSeventhFlatFive, ChordPosition::Root) ),
+            [2,4,2] => Ok( Chord::new(root_note, ChordQuality::DominantSeventhFlatFive, ChordPosition::FirstInversion) ),
             // Supended2Suspended4
-            [2,3,2] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::Root) ),
-            [3,2,5] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::FirstInversion) ),
-            [2,5,2] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::SecondInversion) ),
-            [5,2,3] => Some( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::ThirdInversion) ),
-            _ => None
+            [2,3,2] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::Root) ),
+            [3,2,5] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::FirstInversion) ),
+            [2,5,2] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::SecondInversion) ),
+            [5,2,3] => Ok( Chord::new(root_note, ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::ThirdInversion) ),
+            // NINTH, ELEVENTH AND THIRTEENTH CHORDS
+            _ => all_chord_qualities().into_iter()
+                .filter(|quality| quality.is_extended() && quality.get_intervals().len() == intervals.len() + 1)
+                .flat_map(|quality| CHORD_POSITIONS.iter().take(intervals.len() + 1).map(move |position| Chord::new(root_note, quality, *position)))
+                .find(|chord| {
+                    chord.get_intervals().iter()
+                        .map(|elt| elt.get_value() )
+                        .eq(intervals_by_value.iter().cloned())
+                })
+                .ok_or_else(|| Error::UnknownChordShape(intervals.to_vec()))
         }
     }
     
-    /// Find Chord corresponding to vector of Note
-    pub fn identify(notes: &Vec<Note>) -> Vec<Chord> {
-        let intervals : Vec<Interval> = notes.as_slice()
-            .windows(2)
+    /// Find Chord corresponding to vector of Note, reading the lowest note as a separate bass
+    /// when the notes above it form a chord without it e.g. Am/G. The notes must be stacked in
+    /// order without doublings, identify_pitch_set handling any order and doubled notes
+    pub fn identify(notes: &[Note]) -> Vec<Chord> {
+        let mut res = Chord::identify_chord_tones(notes);
+        if notes.len() > 3 {
+            for chord in Chord::identify_chord_tones(&notes[1..]).iter() {
+                if !chord.get_notes().contains(&notes[0]) {
+                    res.push( chord.with_bass(&notes[0]) );
+                }
+            }
+        }
+        res
+    }
+
+    /// Find every Chord made of the pitch classes of the notes whatever their order or doublings,
+    /// the first note being the bass. Readings with the bass as a chord tone come first
+    pub fn identify_pitch_set(notes: &[Note]) -> Vec<Chord> {
+        let mut pitch_set : Vec<Note> = Vec::new();
+        for note in notes.iter() {
+            if !pitch_set.contains(note) {
+                pitch_set.push( *note );
+            }
+        }
+        let bass_note = match pitch_set.first() {
+            Some(note) => *note,
+            None => return Vec::new()
+        };
+        let mut res : Vec<Chord> = Vec::new();
+        // The notes above a bass outside of the chord make a slash chord
+        for tones in [&pitch_set[..], &pitch_set[1..]].iter() {
+            if tones.len() < 3 {
+                continue;
+            }
+            for root_note in tones.iter() {
+                for (quality, modifiers) in all_chord_shapes().into_iter() {
+                    let chord = Chord::with_modifiers( *root_note, quality, &modifiers, ChordPosition::Root );
+                    let chord_notes = chord.get_notes();
+                    if chord_notes.len() == tones.len() && chord_notes.iter().all(|note| tones.contains(note)) {
+                        res.push( chord.with_bass(&bass_note) );
+                    }
+                }
+            }
+        }
+        res.sort_by_key(|chord| (chord.bass_note.is_some(), chord.position != ChordPosition::Root));
+        res
+    }
+
+    /// Find Chord made of exactly the vector of Note
+    fn identify_chord_tones(notes: &[Note]) -> Vec<Chord> {
+        let intervals : Vec<Interval> = notes.windows(2)
             .map(|w| Interval::from_notes(&w[0], &w[1]) )
             .collect();
         let mut res : Vec<Chord> = Vec::new();
+        if notes.len() > 4 {
+            // Several extended chords share the same interval shape so each is checked against the notes
+            for note in notes.iter() {
+                for quality in all_chord_qualities().into_iter().filter(|quality| quality.get_intervals().len() == notes.len()) {
+                    for position in CHORD_POSITIONS.iter().take(notes.len()) {
+                        let chord = Chord::new( *note, quality, *position );
+                        if chord.get_notes().iter().zip( notes.iter() ).all(|elt| elt.0 == elt.1) {
+                            res.push( chord );
+                        }
+                    }
+                }
+            }
+        } else {
+            for note in notes.iter() {
+                if let Ok(chord) = Chord::from_intervals( *note, &intervals ) {
+                    if chord.get_notes().iter().zip( notes.iter() ).all(|elt| elt.0 == elt.1) {
+                        res.push( chord );
+                    }
+                }
+            }
+        }
+        // Chords with added or altered tones
         for note in notes.iter() {
-            if let Some(chord) = Chord::from_intervals( note.clone(), &intervals ) {
-                if chord.get_notes().iter().zip( notes.iter() ).all(|elt| elt.0 == elt.1) {
-                    res.push( chord );
+            for (quality, modifiers) in modified_chord_shapes().into_iter() {
+                let tones_count = Chord::with_modifiers( *note, quality, &modifiers, ChordPosition::Root ).get_notes().len();
+                if tones_count != notes.len() {
+                    continue;
+                }
+                for position in CHORD_POSITIONS.iter().take(tones_count) {
+                    let chord = Chord::with_modifiers( *note, quality, &modifiers, *position );
+                    if chord.get_notes().iter().zip( notes.iter() ).all(|elt| elt.0 == elt.1) {
+                        res.push( chord );
+                    }
                 }
             }
         }
@@ -212,127 +674,96 @@ impl Chord{
 
     pub fn get_root(&self) -> Note { self.root_note }
     pub fn get_quality(&self) -> ChordQuality { self.quality }
+    pub fn get_modifiers(&self) -> Vec<ChordModifier> { self.modifiers.clone() }
+    pub fn get_position(&self) -> ChordPosition { self.position }
+    pub fn get_bass_note(&self) -> Option<Note> { self.bass_note }
 
-    pub fn get_intervals(&self) -> Vec<Interval> {
-        let interval_names = match (self.quality,self.position) {
-            // Major
-            (ChordQuality::Major, ChordPosition::Root) => vec!["M3","m3"],
-            (ChordQuality::Major, ChordPosition::FirstInversion) => vec!["m3","P4"],
-            (ChordQuality::Major, ChordPosition::SecondInversion) => vec!["P4","M3"],
-            // Minor
-            (ChordQuality::Minor, ChordPosition::Root) => vec!["m3","M3"],
-            (ChordQuality::Minor, ChordPosition::FirstInversion) => vec!["M3","P4"],
-            (ChordQuality::Minor, ChordPosition::SecondInversion) => vec!["P4","m3"],
-            // Diminished
-            (ChordQuality::Diminished, ChordPosition::Root) => vec!["m3","m3"],
-            (ChordQuality::Diminished, ChordPosition::FirstInversion) => vec!["m3","d5"],
-            (ChordQuality::Diminished, ChordPosition::SecondInversion) => vec!["d5","m3"],
-            // Augmented
-            (ChordQuality::Augmented, ChordPosition::Root) => vec!["M3","M3"],
-            // Suspended2
-            (ChordQuality::SuspendedTwo, ChordPosition::Root) => vec!["M2","P4"],
-            (ChordQuality::SuspendedTwo, ChordPosition::FirstInversion) => vec!["P4","P4"],
-            // Suspended4
-            (ChordQuality::SuspendedFour, ChordPosition::Root) => vec!["P4","M2"],
-            // Flat5
-            (ChordQuality::FlatFive, ChordPosition::Root) => vec!["M3","M2"],
-            (ChordQuality::FlatFive, ChordPosition::FirstInversion) => vec!["M2","d5"],
-            (ChordQuality::FlatFive, ChordPosition::SecondInversion) => vec!["d5","M3"],
-            // Suspended2Flat5
-            (ChordQuality::SuspendedTwoFlatFive, ChordPosition::Root) => vec!["M2","M3"],
-            (ChordQuality::SuspendedTwoFlatFive, ChordPosition::FirstInversion) => vec!["M3","d5"],
-            (ChordQuality::SuspendedTwoFlatFive, ChordPosition::SecondInversion) => vec!["d5","M2"],
-            // Dominant7
-            (ChordQuality::DominantSeventh, ChordPosition::Root) => vec!["M3","m3","m3"],
-            (ChordQuality::DominantSeventh, ChordPosition::FirstInversion) => vec!["m3","m3","M2"],
-            (ChordQuality::DominantSeventh, ChordPosition::SecondInversion) => vec!["m3","M2","M3"],
-            (ChordQuality::DominantSeventh, ChordPosition::ThirdInversion) => vec!["M2","M3","m3"],
-            // Major7
-            (ChordQuality::MajorSeventh, ChordPosition::Root) => vec!["M3","m3","M3"],
-            (ChordQuality::MajorSeventh, ChordPosition::FirstInversion) => vec!["m3","M3","m2"],
-            (ChordQuality::MajorSeventh, ChordPosition::SecondInversion) => vec!["M3","m2","M3"],
-            (ChordQuality::MajorSeventh, ChordPosition::ThirdInversion) => vec!["m2","M3","m3"],
-            // Minor7
-            (ChordQuality::MinorSeventh, ChordPosition::Root) => vec!["m3","M3","m3"],
-            (ChordQuality::MinorSeventh, ChordPosition::FirstInversion) => vec!["M3","m3","M2"],
-            (ChordQuality::MinorSeventh, ChordPosition::SecondInversion) => vec!["m3","M2","m3"],
-            (ChordQuality::MinorSeventh, ChordPosition::ThirdInversion) => vec!["M2","m3","M3"],
-            // MinorMajor7
-            (ChordQuality::MinorMajorSeventh, ChordPosition::Root) => vec!["m3","M3","M3"],
-            (ChordQuality::MinorMajorSeventh, ChordPosition::FirstInversion) => vec!["M3","M3","m2"],
-            (ChordQuality::MinorMajorSeventh, ChordPosition::SecondInversion) => vec!["M3","m2","m3"],
-            (ChordQuality::MinorMajorSeventh, ChordPosition::ThirdInversion) => vec!["m2","m3","M3"],
-            // Diminished7
-            (ChordQuality::DiminishedSeventh, ChordPosition::Root) => vec!["m3","m3","m3"],
-            // Augmented7
-            (ChordQuality::AugmentedSeventh, ChordPosition::Root) => vec!["M3","M3","M2"],
-            (ChordQuality::AugmentedSeventh, ChordPosition::FirstInversion) => vec!["M3","M2","M2"],
-            (ChordQuality::AugmentedSeventh, ChordPosition::SecondInversion) => vec!["M2","M2","M3"],
-            (ChordQuality::AugmentedSeventh, ChordPosition::ThirdInversion) => vec!["M2","M3","M3"],
-            // AugmentedMajor7
-            (ChordQuality::AugmentedMajorSeventh, ChordPosition::Root) => vec!["M3","M3","m3"],
-            (ChordQuality::AugmentedMajorSeventh, ChordPosition::FirstInversion) => vec!["M3","m3","m2"],
-            (ChordQuality::AugmentedMajorSeventh, ChordPosition::SecondInversion) => vec!["m3","m2","M3"],
-            (ChordQuality::AugmentedMajorSeventh, ChordPosition::ThirdInversion) => vec!["m2","M3","M3"],
-            // Major7Flat5
-            (ChordQuality::MajorSeventhFlatFive, ChordPosition::Root) => vec!["M3","M2","P4"],
-            (ChordQuality::MajorSeventhFlatFive, ChordPosition::FirstInversion) => vec!["M2","P4","m2"],
-            (ChordQuality::MajorSeventhFlatFive, ChordPosition::SecondInversion) => vec!["P4","m2","M3"],
-            (ChordQuality::MajorSeventhFlatFive, ChordPosition::ThirdInversion) => vec!["m2","M3","M2"],
-            // Minor7Flat5
-            (ChordQuality::MinorSeventhFlatFive, ChordPosition::Root) => vec!["m3","m3","M3"],
-            (ChordQuality::MinorSeventhFlatFive, ChordPosition::FirstInversion) => vec!["m3","M3","M2"],
-            (ChordQuality::MinorSeventhFlatFive, ChordPosition::SecondInversion) => vec!["M3","M2","m3"],
-            (ChordQuality::MinorSeventhFlatFive, ChordPosition::ThirdInversion) => vec!["M2","m3","m3"],
-            // Dominant7Flat5
-            (ChordQuality::DominantSeventhFlatFive, ChordPosition::Root) => vec!["M3","M2","M3"],
-            (ChordQuality::DominantSeventhFlatFive, ChordPosition::FirstInversion) => vec!["M2","M3","M2"],
-            // Suspended2Suspended4
-            (ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::Root) => vec!["M2","m3","M2"],
-            (ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::FirstInversion) => vec!["m3","M2","P4"],
-            (ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::SecondInversion) => vec!["M2","P4","M2"],
-            (ChordQuality::SuspendedTwoSuspendedFour, ChordPosition::ThirdInversion) => vec!["P4","M2","m3"],
-            _ => vec![]
+    /// Get the lowest note of the Chord, whether a chord tone or a separate bass note
+    pub fn get_lowest_note(&self) -> Note {
+        self.get_notes()[0]
+    }
 
-        };
-        let intervals : Vec<Interval> = interval_names.iter()
-            .map(|s| Interval::from_name(s).unwrap() )
+    /// Get the Intervals of the chord tones from the root once the modifiers are applied
+    pub fn get_tones(&self) -> Vec<Interval> {
+        let altered_numbers : Vec<u32> = self.modifiers.iter()
+            .filter_map(|modifier| match modifier {
+                ChordModifier::Alter(interval) => Some( interval.get_number() ),
+                _ => None
+            })
             .collect();
-        intervals
+        let mut res : Vec<Interval> = self.quality.get_intervals().into_iter()
+            .filter(|interval| !altered_numbers.contains(&interval.get_number()) )
+            .collect();
+        for modifier in self.modifiers.iter() {
+            match modifier {
+                ChordModifier::Add(interval) | ChordModifier::Alter(interval) => {
+                    if !res.contains(interval) {
+                        res.push( *interval );
+                    }
+                },
+                ChordModifier::Omit(number) => res.retain(|interval| interval.get_number() != *number),
+                ChordModifier::Suspend(interval) => {
+                    res.retain(|tone| tone.get_number() != 3);
+                    if !res.contains(interval) {
+                        res.push( *interval );
+                    }
+                }
+            }
+        }
+        res.sort();
+        res
+    }
+
+    /// Get the Intervals between consecutive notes of the Chord
+    pub fn get_intervals(&self) -> Vec<Interval> {
+        self.get_notes().windows(2)
+            .map(|w| Interval::from_notes(&w[0], &w[1]) )
+            .collect()
     }
 
-    /// Get the notes of the Chord
+    /// Get the notes of the Chord, spelled by stacking letters from the root, after the bass note if any
     pub fn get_notes(&self) -> Vec<Note> { 
-        let mut res = vec![ self.root_note.clone() ];
-        let intervals = Self::new(self.root_note.clone(), self.quality.clone(), ChordPosition::Root ).get_intervals();
-        for i in 0..intervals.len() {
-            let note = intervals[i].apply( &res[i] );
-            res.push( note );
-        }
+        let res : Vec<Note> = self.get_tones().iter()
+            .map(|interval| interval.apply( &self.root_note ) )
+            .collect();
         let n = res.len();
-        match self.position {
+        let upper_notes = match self.position {
             ChordPosition::Root => res,
             ChordPosition::FirstInversion => res.into_iter().cycle().skip(1).take(n).collect::<Vec<Note>>(),
             ChordPosition::SecondInversion => res.into_iter().cycle().skip(2).take(n).collect::<Vec<Note>>(),
-            ChordPosition::ThirdInversion => res.into_iter().cycle().skip(3).take(n).collect::<Vec<Note>>()
+            ChordPosition::ThirdInversion => res.into_iter().cycle().skip(3).take(n).collect::<Vec<Note>>(),
+            ChordPosition::FourthInversion => res.into_iter().cycle().skip(4).take(n).collect::<Vec<Note>>(),
+            ChordPosition::FifthInversion => res.into_iter().cycle().skip(5).take(n).collect::<Vec<Note>>(),
+            ChordPosition::SixthInversion => res.into_iter().cycle().skip(6).take(n).collect::<Vec<Note>>()
+        };
+        match self.bass_note {
+            Some(bass_note) => std::iter::once(bass_note).chain(upper_notes).collect(),
+            None => upper_notes
         }
     }
 
+    /// Get the notes of the Chord spelled following a policy
+    pub fn get_notes_with(&self, policy: &SpellingPolicy) -> Vec<Note> {
+        self.get_notes().iter()
+            .map(|note| policy.spell(note) )
+            .collect()
+    }
+
     /// Get chord Intervals from root note
     pub fn get_intervals_from_root(&self) -> Vec<Interval> {
         let mut root_intervals : Vec<Interval> = self.get_intervals().iter()
-            .scan(0, |state, x| {
-                *state = *state + x.get_value();
-                Some( Interval::new(*state) )
+            .scan(Interval::new(0), |state, x| {
+                *state = *state + *x;
+                Some( *state )
             }).collect();
         root_intervals.insert(0, Interval::new(0));
         root_intervals
     }
 
     /// Prints a chord numeral based on a scale root note e.g. Eb from C is IIIb
-    pub fn as_numeral(&self, scale_root: &Note, index: usize) -> String {
-        let interval_name = Interval::from_notes(scale_root, &self.root_note).to_string();
-        let numeral = match interval_name.as_str() {
+    pub fn as_numeral(&self, scale_root: &Note, index: usize) -> Result<String, Error> {
+        let interval = Interval::new( Interval::from_notes(scale_root, &self.root_note).get_value() % 12 );
+        let numeral = match interval.to_string().as_str() {
             "P1" => "I",
             "m2" => "bII",
             "M2" => match index {
@@ -372,15 +803,77 @@ impl Chord{
                 _ => "bVII"
             },
             "M7" => "VII",
-            _ => ""
+            _ => return Err(Error::UnknownInterval(interval))
         };
-        numeral.to_string()
+        Ok( numeral.to_string() )
     }
 }
 
 impl fmt::Display for Chord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.root_note, self.quality.to_string())
+        write!(f, "{}{}", self.root_note, self.quality)?;
+        let sixth = ChordModifier::Add(Interval::from_name("M6").unwrap());
+        let ninth = ChordModifier::Add(Interval::from_name("M9").unwrap());
+        let mut modifiers = self.modifiers.clone();
+        if self.quality == ChordQuality::DominantSeventh && modifiers == altered_modifiers() {
+            write!(f, "alt")?;
+            modifiers.clear();
+        } else if modifiers.contains(&sixth) && modifiers.contains(&ninth) {
+            write!(f, "6/9")?;
+            modifiers.retain(|modifier| *modifier != sixth && *modifier != ninth);
+        } else if modifiers.contains(&sixth) {
+            write!(f, "6")?;
+            modifiers.retain(|modifier| *modifier != sixth);
+        }
+        for modifier in modifiers.iter().filter(|modifier| matches!(modifier, ChordModifier::Suspend(_))) {
+            write!(f, "{}", modifier)?;
+        }
+        modifiers.retain(|modifier| !matches!(modifier, ChordModifier::Suspend(_)));
+        for modifier in modifiers.iter().filter(|modifier| matches!(modifier, ChordModifier::Add(_))) {
+            write!(f, "{}", modifier)?;
+        }
+        let others : Vec<String> = modifiers.iter()
+            .filter(|modifier| !matches!(modifier, ChordModifier::Add(_)))
+            .map(|modifier| modifier.to_string())
+            .collect();
+        if !others.is_empty() {
+            write!(f, "({})", others.join(","))?;
+        }
+        match self.bass_note {
+            Some(bass_note) => write!(f, "/{}", bass_note),
+            None if self.get_lowest_note() != self.root_note => write!(f, "/{}", self.get_lowest_note()),
+            None => Ok(())
+        }
+    }
+}
+
+/// Parses chord symbols such as "Cmaj7", "F#m7b5", "Bb7(#9)", "C7sus4", "C6/9", "Am/C" or "F/G", a bass note
+/// after a slash selecting the matching inversion when it belongs to the chord
+impl FromStr for Chord {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || Error::UnknownName { kind: "Chord", name: s.to_string() };
+        let (root_note, rest) = parse_note_prefix(s)?;
+        let (suffix, bass_note) = match rest.rfind('/') {
+            Some(idx) => match rest[idx + 1..].parse::<Note>() {
+                Ok(bass_note) => (&rest[..idx], Some(bass_note)),
+                Err(_) => (rest, None)
+            },
+            None => (rest, None)
+        };
+        let (quality, modifiers) = parse_chord_suffix(suffix).ok_or_else(unknown)?;
+        let chord = Chord::with_modifiers(root_note, quality, &modifiers, ChordPosition::Root);
+        match bass_note {
+            Some(bass_note) => Ok( chord.with_bass(&bass_note) ),
+            None => Ok(chord)
+        }
+    }
+}
+
+impl TryFrom<&str> for Chord {
+    type Error = Error;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
     }
 }
 
@@ -0,0 +1,80 @@
+use crate::{Note, NoteAccidental, Key, KeyQuality, Error};
+use crate::key::SHARP_ORDER;
+
+/// Largest number of sharps or flats kept when stepping around the circle
+const MAX_SIGNATURE : i32 = 6;
+
+/// Returns the note's position on the line of fifths counted from C e.g. 1 for G and -2 for Bb
+pub fn fifths_position(note: &Note) -> i32 {
+    let letter_position = SHARP_ORDER.iter().position(|&elt| elt == note.get_letter()).unwrap_or(0) as i32 - 1;
+    letter_position + 7 * note.get_accidental().get_offset()
+}
+
+/// Returns the note's position on the circle of fifths clockwise from C, enharmonic notes sharing a position
+pub fn circle_position(note: &Note) -> u32 {
+    fifths_position(note).rem_euclid(12) as u32
+}
+
+/// Creates the key of the given quality with the given number of sharps, flats being counted as negative.
+/// Fails if the tonic would need more than a double sharp or flat
+pub fn key_from_signature(signature: i32, quality: KeyQuality) -> Result<Key, Error> {
+    let position = match quality {
+        KeyQuality::Major => signature,
+        KeyQuality::Minor => signature + 3
+    };
+    let letter = SHARP_ORDER[(position + 1).rem_euclid(7) as usize];
+    let offset = (position + 1).div_euclid(7);
+    match NoteAccidental::from_offset(offset) {
+        Some( accidental ) => Ok( Key::new( Note::new(letter, accidental), quality ) ),
+        None => Err( Error::UnspellableNote { letter, offset } )
+    }
+}
+
+/// Returns the enharmonically equivalent key on the other side of the circle e.g. Gb major for F# major
+pub fn enharmonic_key(key: &Key) -> Key {
+    let signature = match key.get_signature() {
+        0 => return *key,
+        s if s > 0 => s - 12,
+        s => s + 12
+    };
+    key_from_signature(signature, key.get_quality()).expect("keys within 12 fifths of C are spellable")
+}
+
+/// Moves a key clockwise around the circle by a number of fifths, wrapping to the enharmonic
+/// key once more than six sharps would be needed
+pub fn step_clockwise(key: &Key, steps: u32) -> Key {
+    let mut signature = key.get_signature() + steps as i32;
+    while signature > MAX_SIGNATURE {
+        signature -= 12;
+    }
+    key_from_signature(signature, key.get_quality()).expect("signatures between a key's and six sharps are spellable")
+}
+
+/// Moves a key counter-clockwise around the circle by a number of fifths, wrapping to the
+/// enharmonic key once more than six flats would be needed
+pub fn step_counter_clockwise(key: &Key, steps: u32) -> Key {
+    let mut signature = key.get_signature() - steps as i32;
+    while signature < -MAX_SIGNATURE {
+        signature += 12;
+    }
+    key_from_signature(signature, key.get_quality()).expect("signatures between a key's and six flats are spellable")
+}
+
+/// Returns the shortest number of fifths between two key signatures, positive when going clockwise
+pub fn circle_distance(from: &Key, to: &Key) -> i32 {
+    let distance = (to.get_signature() - from.get_signature()).rem_euclid(12);
+    if distance > 6 { distance - 12 } else { distance }
+}
+
+/// Returns the keys whose signatures differ by at most one accidental: the relative key, then the
+/// dominant and subdominant keys followed by their relatives. Keys past double accidentals are left out
+pub fn closely_related_keys(key: &Key) -> Vec<Key> {
+    let mut res = vec![ key.relative() ];
+    for signature in [key.get_signature() + 1, key.get_signature() - 1].iter() {
+        if let Ok(neighbour) = key_from_signature(*signature, key.get_quality()) {
+            res.push( neighbour );
+            res.push( neighbour.relative() );
+        }
+    }
+    res
+}
@@ -0,0 +1,175 @@
+use crate::{Note, MIDINote, Fretboard, FretPosition, Fingering};
+
+/// Lowest number of frets drawn in a chord box
+const CHORD_BOX_FRETS : u32 = 4;
+
+/// Sizes in pixels of the fretboard diagrams
+const FRET_WIDTH : u32 = 40;
+const STRING_SPACING : u32 = 20;
+const FRETBOARD_MARGIN : u32 = 30;
+
+/// Sizes in pixels of the keyboard diagrams
+const WHITE_KEY_WIDTH : u32 = 20;
+const WHITE_KEY_HEIGHT : u32 = 100;
+const BLACK_KEY_WIDTH : u32 = 12;
+const BLACK_KEY_HEIGHT : u32 = 60;
+
+/// Fill colour of highlighted notes
+const HIGHLIGHT_COLOUR : &str = "#3366cc";
+
+/// Renders a fingering as a plain-text chord box, strings from the thickest going left to right.
+/// Muted strings are marked x and open ones o above the nut, fretted notes being drawn as O.
+/// Fingerings out of reach of the nut are labelled with the fret of their first row, and
+/// fingerings without strings give an empty string
+pub fn chord_box(fingering: &Fingering) -> String {
+    let frets = fingering.get_frets();
+    if frets.is_empty() {
+        return String::new();
+    }
+    let barre = fingering.get_barre();
+    let highest = frets.iter().filter_map(|fret| *fret).max().unwrap_or(0);
+    let start = if highest <= CHORD_BOX_FRETS { 1 } else { fingering.get_position() };
+    let end = highest.max(start + CHORD_BOX_FRETS - 1);
+    let barred = |string: usize, fret: u32| barre
+        .map(|barre| barre.get_fret() == fret && string >= barre.get_first_string() && string <= barre.get_last_string())
+        .unwrap_or(false);
+
+    let header : Vec<&str> = frets.iter()
+        .map(|fret| match fret {
+            None => "x",
+            Some(0) => "o",
+            Some(_) => " "
+        })
+        .collect();
+    let mut lines = vec![ header.join(" ").trim_end().to_string() ];
+    if start == 1 {
+        lines.push( "=".repeat(2 * frets.len() - 1) );
+    }
+    for fret in start..=end {
+        let mut line = String::new();
+        for (string, elt) in frets.iter().enumerate() {
+            if string > 0 {
+                line.push( if barred(string - 1, fret) && barred(string, fret) { '-' } else { ' ' } );
+            }
+            line.push( if *elt == Some(fret) || barred(string, fret) { 'O' } else { '|' } );
+        }
+        if fret == start && start > 1 {
+            line.push_str( &format!(" {}fr", start) );
+        }
+        lines.push( line );
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Opens an SVG document of the given size
+fn svg_header(width: u32, height: u32) -> String {
+    format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n", width, height)
+}
+
+/// Renders positions on a fretboard between two frets included as a standalone SVG document,
+/// the thickest string at the bottom. Each position is drawn as a dot labelled with its note,
+/// open strings being drawn left of the nut. A fretboard without strings gives an empty document
+pub fn fretboard_svg(fretboard: &Fretboard, positions: &[(FretPosition, MIDINote)], min_fret: u32, max_fret: u32) -> String {
+    let strings = fretboard.get_string_count() as u32;
+    if strings == 0 {
+        return svg_header(0, 0) + "</svg>\n";
+    }
+    let max_fret = max_fret.min(fretboard.get_frets()).max(min_fret);
+    let first_wire = min_fret.saturating_sub(1);
+    let wire_x = |fret: u32| FRETBOARD_MARGIN + (fret - first_wire) * FRET_WIDTH;
+    let string_y = |string: u32| FRETBOARD_MARGIN + (strings - 1 - string) * STRING_SPACING;
+    let bottom = string_y(0);
+    let width = wire_x(max_fret) + FRETBOARD_MARGIN;
+    let height = bottom + FRETBOARD_MARGIN;
+
+    let mut res = svg_header(width, height);
+    for fret in first_wire..=max_fret {
+        let stroke = if fret == 0 { 4 } else { 1 };
+        res.push_str( &format!("<line x1=\"{0}\" y1=\"{1}\" x2=\"{0}\" y2=\"{2}\" stroke=\"black\" stroke-width=\"{3}\"/>\n",
+            wire_x(fret), FRETBOARD_MARGIN, bottom, stroke) );
+    }
+    for string in 0..strings {
+        res.push_str( &format!("<line x1=\"{0}\" y1=\"{1}\" x2=\"{2}\" y2=\"{1}\" stroke=\"black\"/>\n",
+            wire_x(first_wire), string_y(string), wire_x(max_fret)) );
+    }
+    for fret in (first_wire + 1)..=max_fret {
+        res.push_str( &format!("<text x=\"{}\" y=\"{}\" font-size=\"10\" text-anchor=\"middle\">{}</text>\n",
+            wire_x(fret) - FRET_WIDTH / 2, height - 8, fret) );
+    }
+    for (position, note) in positions.iter() {
+        if position.get_fret() < min_fret || position.get_fret() > max_fret || position.get_string() as u32 >= strings {
+            continue;
+        }
+        let x = if position.get_fret() == 0 { wire_x(0) - STRING_SPACING / 2 - 5 } else { wire_x(position.get_fret()) - FRET_WIDTH / 2 };
+        let y = string_y(position.get_string() as u32);
+        res.push_str( &format!("<circle cx=\"{}\" cy=\"{}\" r=\"8\" fill=\"{}\"/>\n", x, y, HIGHLIGHT_COLOUR) );
+        res.push_str( &format!("<text x=\"{}\" y=\"{}\" font-size=\"9\" text-anchor=\"middle\" fill=\"white\">{}</text>\n",
+            x, y + 3, note.get_note()) );
+    }
+    res.push_str( "</svg>\n" );
+    res
+}
+
+/// Checks if a MIDI note index is played on a black key
+fn is_black_key(index: u32) -> bool {
+    matches!(index % 12, 1 | 3 | 6 | 8 | 10)
+}
+
+/// Renders the keys between two notes widened to white keys, labelling the highlighted ones
+fn keyboard(lowest: &MIDINote, highest: &MIDINote, label: impl Fn(u32) -> Option<String>) -> String {
+    let mut low = lowest.get_index().min(highest.get_index());
+    let mut high = lowest.get_index().max(highest.get_index());
+    while is_black_key(low) {
+        low -= 1;
+    }
+    while is_black_key(high) {
+        high += 1;
+    }
+    // Left edge of each key, black keys straddling the white key boundary below them
+    let mut keys : Vec<(u32, u32)> = Vec::new();
+    let mut white_keys = 0;
+    for index in low..=high {
+        if is_black_key(index) {
+            keys.push( (index, white_keys * WHITE_KEY_WIDTH - BLACK_KEY_WIDTH / 2) );
+        } else {
+            keys.push( (index, white_keys * WHITE_KEY_WIDTH) );
+            white_keys += 1;
+        }
+    }
+
+    let mut res = svg_header(white_keys * WHITE_KEY_WIDTH, WHITE_KEY_HEIGHT);
+    for black in [false, true].iter() {
+        for (index, x) in keys.iter().filter(|key| is_black_key(key.0) == *black) {
+            let (width, height, colour) = if *black {
+                (BLACK_KEY_WIDTH, BLACK_KEY_HEIGHT, "black")
+            } else {
+                (WHITE_KEY_WIDTH, WHITE_KEY_HEIGHT, "white")
+            };
+            let name = label(*index);
+            let fill = if name.is_some() { HIGHLIGHT_COLOUR } else { colour };
+            res.push_str( &format!("<rect x=\"{}\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"black\"/>\n", x, width, height, fill) );
+            if let Some(name) = name {
+                res.push_str( &format!("<text x=\"{}\" y=\"{}\" font-size=\"9\" text-anchor=\"middle\" fill=\"white\">{}</text>\n",
+                    x + width / 2, height - 6, name) );
+            }
+        }
+    }
+    res.push_str( "</svg>\n" );
+    res
+}
+
+/// Renders a piano keyboard between two notes as a standalone SVG document, highlighting the keys
+/// of a voicing's MIDI notes. The keyboard starts and ends on white keys
+pub fn keyboard_svg(notes: &[MIDINote], lowest: &MIDINote, highest: &MIDINote) -> String {
+    keyboard(lowest, highest, |index| notes.iter()
+        .find(|note| note.get_index() == index)
+        .map(|note| note.get_note().to_string()))
+}
+
+/// Renders a piano keyboard between two notes as a standalone SVG document, highlighting every key
+/// of the notes in any octave e.g. the notes of a chord or a mode
+pub fn keyboard_svg_for_notes(notes: &[Note], lowest: &MIDINote, highest: &MIDINote) -> String {
+    keyboard(lowest, highest, |index| notes.iter()
+        .find(|note| note.get_index() == index % 12)
+        .map(|note| note.to_string()))
+}
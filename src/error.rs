@@ -0,0 +1,71 @@
+use std::fmt;
+use crate::{Interval, MIDINote, NoteLetter, ParseNoteError};
+
+/// Errors returned across the crate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A note name could not be parsed
+    ParseNote(ParseNoteError),
+    /// A name does not correspond to any known value of the given kind
+    UnknownName { kind: &'static str, name: String },
+    /// A MIDI number falls outside of the 0 to 127 range
+    MidiNumberOutOfRange(i64),
+    /// A sequence of intervals does not match any known chord
+    UnknownChordShape(Vec<Interval>),
+    /// An interval has no name in the requested context
+    UnknownInterval(Interval),
+    /// A pitch cannot be written with the given letter without going past double accidentals
+    UnspellableNote { letter: NoteLetter, offset: i32 },
+    /// A mode does not correspond to a major or minor key signature
+    NoKeySignature(String),
+    /// A chord has notes outside of a mode
+    ChordNotInMode { chord: String, mode: String },
+    /// A string or fret does not exist on the fretboard
+    InvalidFretPosition { string: usize, fret: u32 },
+    /// Tablature text is not laid out as expected
+    InvalidTab(String),
+    /// Notes cannot be played at the same time on a fretboard
+    UnplayableNotes(Vec<MIDINote>),
+    /// Bytes do not form a supported Standard MIDI File
+    InvalidMidiFile(String)
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseNote(err) => write!(f, "Failed to parse note: {}", err),
+            Self::UnknownName { kind, name } => write!(f, "No {} found by the name '{}'", kind, name),
+            Self::MidiNumberOutOfRange(num) => write!(f, "MIDI number {} is outside of the 0 to 127 range", num),
+            Self::UnknownChordShape(intervals) => {
+                let names : Vec<String> = intervals.iter().map(|interval| interval.to_string()).collect();
+                write!(f, "No chord found for intervals [{}]", names.join(","))
+            },
+            Self::UnknownInterval(interval) => write!(f, "Interval {} has no name in this context", interval),
+            Self::UnspellableNote { letter, offset } => write!(f, "Letter {:?} cannot be shifted by {} semitones", letter, offset),
+            Self::NoKeySignature(mode) => write!(f, "Mode {} does not correspond to a key signature", mode),
+            Self::ChordNotInMode { chord, mode } => write!(f, "Chord {} is not part of mode {}", chord, mode),
+            Self::InvalidFretPosition { string, fret } => write!(f, "No fret {} on string {} of the fretboard", fret, string),
+            Self::InvalidTab(reason) => write!(f, "Failed to parse tablature: {}", reason),
+            Self::UnplayableNotes(notes) => {
+                let names : Vec<String> = notes.iter().map(|note| note.to_string()).collect();
+                write!(f, "Notes [{}] cannot be played together on the fretboard", names.join(","))
+            },
+            Self::InvalidMidiFile(reason) => write!(f, "Failed to read MIDI file: {}", reason)
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseNote(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<ParseNoteError> for Error {
+    fn from(err: ParseNoteError) -> Self {
+        Self::ParseNote(err)
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+use std::fmt;
+use crate::{Note, MIDINote, Interval, IntervalQuality, Chord, Fretboard, FretPosition};
+
+/// Most fingers available to fret notes
+const MAX_FINGERS : usize = 4;
+
+/// A finger pressing several strings on the same fret
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Barre {
+    fret: u32,
+    first_string: usize,
+    last_string: usize
+}
+
+impl Barre {
+    pub fn get_fret(&self) -> u32 { self.fret }
+    pub fn get_first_string(&self) -> usize { self.first_string }
+    pub fn get_last_string(&self) -> usize { self.last_string }
+}
+
+/// A way of playing a chord on a fretboard, giving the fret of each string from the thickest,
+/// None being a muted string and 0 an open one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fingering {
+    frets: Vec<Option<u32>>,
+    barre: Option<Barre>
+}
+
+impl Fingering {
+
+    /// Creates a fingering from the fret of each string, a barre being used when there are not enough fingers otherwise
+    pub fn new(frets: Vec<Option<u32>>) -> Fingering {
+        let barre = find_barre(&frets);
+        Fingering{ frets, barre }
+    }
+
+    pub fn get_frets(&self) -> Vec<Option<u32>> { self.frets.clone() }
+    pub fn get_barre(&self) -> Option<Barre> { self.barre }
+
+    /// Returns the MIDI notes sounding on a fretboard from the thickest string
+    pub fn get_notes(&self, fretboard: &Fretboard) -> Vec<MIDINote> {
+        self.frets.iter().enumerate()
+            .filter_map(|(string, fret)| fret.and_then(|fret| fretboard.get_note(&FretPosition::new(string, fret)).ok()))
+            .collect()
+    }
+
+    /// Returns the positions of the sounding strings along with the MIDI notes played
+    pub fn get_positions(&self, fretboard: &Fretboard) -> Vec<(FretPosition, MIDINote)> {
+        self.frets.iter().enumerate()
+            .filter_map(|(string, fret)| fret.map(|fret| FretPosition::new(string, fret)))
+            .filter_map(|position| fretboard.get_note(&position).ok().map(|note| (position, note)))
+            .collect()
+    }
+
+    /// Returns the lowest fretted fret, 0 when every sounding string is open
+    pub fn get_position(&self) -> u32 {
+        self.fretted().min().unwrap_or(0)
+    }
+
+    /// Returns the number of frets between the lowest and highest fretted notes
+    pub fn get_span(&self) -> u32 {
+        self.fretted().max().unwrap_or(0) - self.get_position()
+    }
+
+    /// Returns the number of fingers needed, a barre counting as one
+    pub fn get_finger_count(&self) -> usize {
+        finger_count(&self.frets, self.barre)
+    }
+
+    pub fn get_muted_count(&self) -> usize { self.frets.iter().filter(|fret| fret.is_none()).count() }
+    pub fn get_open_count(&self) -> usize { self.frets.iter().filter(|fret| **fret == Some(0)).count() }
+
+    /// Returns how hard the fingering is to play, easy fingerings having low values. Fingers, fret span,
+    /// barres, muted strings between or above sounding ones and positions up the neck add to the
+    /// difficulty while open and sounding strings lower it
+    pub fn get_difficulty(&self) -> f64 {
+        let sounding : Vec<usize> = self.frets.iter().enumerate()
+            .filter(|(_, fret)| fret.is_some())
+            .map(|(string, _)| string)
+            .collect();
+        let (inner_muted, upper_muted) = match (sounding.first(), sounding.last()) {
+            (Some(first), Some(last)) => (
+                self.frets[*first..=*last].iter().filter(|fret| fret.is_none()).count(),
+                self.frets.len() - 1 - last
+            ),
+            _ => (0, 0)
+        };
+        self.get_finger_count() as f64
+            + self.get_span() as f64
+            + if self.barre.is_some() { 1.5 } else { 0.0 }
+            + 2.0 * inner_muted as f64
+            + 1.5 * upper_muted as f64
+            + self.get_position() as f64 / 4.0
+            - 0.5 * self.get_open_count() as f64
+            - 0.5 * sounding.len() as f64
+    }
+
+    fn fretted(&self) -> impl Iterator<Item = u32> + '_ {
+        self.frets.iter().filter_map(|fret| *fret).filter(|fret| *fret > 0)
+    }
+}
+
+impl fmt::Display for Fingering {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names : Vec<String> = self.frets.iter()
+            .map(|fret| fret.map(|fret| fret.to_string()).unwrap_or_else(|| String::from("x")))
+            .collect();
+        let separator = if self.frets.iter().any(|fret| fret.unwrap_or(0) > 9) { "-" } else { "" };
+        write!(f, "{}", names.join(separator))
+    }
+}
+
+/// Counts the fingers fretting the notes, a barre pressing every note of its fret within its strings
+fn finger_count(frets: &[Option<u32>], barre: Option<Barre>) -> usize {
+    let fretted = frets.iter().enumerate().filter(|(_, fret)| fret.unwrap_or(0) > 0);
+    match barre {
+        Some(barre) => 1 + fretted
+            .filter(|(string, fret)| !(**fret == Some(barre.fret) && *string >= barre.first_string && *string <= barre.last_string))
+            .count(),
+        None => fretted.count()
+    }
+}
+
+/// Finds a barre on the lowest fretted fret reaching the highest string it is needed on, kept only when
+/// there are not enough fingers otherwise. Strings under a barre cannot be open or muted
+fn find_barre(frets: &[Option<u32>]) -> Option<Barre> {
+    let fret = frets.iter().filter_map(|fret| *fret).filter(|fret| *fret > 0).min()?;
+    let strings : Vec<usize> = frets.iter().enumerate()
+        .filter(|(_, elt)| **elt == Some(fret))
+        .map(|(string, _)| string)
+        .collect();
+    let first_string = *strings.first()?;
+    let last_string = *strings.last()?;
+    if strings.len() < 2 || frets[first_string..=last_string].iter().any(|elt| elt.unwrap_or(0) < fret) {
+        return None;
+    }
+    let barre = Barre{ fret, first_string, last_string };
+    if finger_count(frets, None) > MAX_FINGERS && finger_count(frets, Some(barre)) < finger_count(frets, None) {
+        Some( barre )
+    } else {
+        None
+    }
+}
+
+/// Returns the chord tones which a fingering has to sound. The fifth of chords with more than three
+/// tones, the ninth of elevenths and the eleventh of thirteenths can be left out
+fn required_tones(chord: &Chord) -> Vec<Note> {
+    let tones = chord.get_tones();
+    let count = tones.len();
+    tones.iter()
+        .filter(|interval| match (interval.get_number(), interval.get_quality()) {
+            (5, IntervalQuality::Perfect) => count <= 3,
+            (9, IntervalQuality::Major) => count <= 5,
+            (11, IntervalQuality::Perfect) => count <= 6,
+            _ => true
+        })
+        .map(|interval: &Interval| interval.apply(&chord.get_root()))
+        .collect()
+}
+
+/// Returns the playable fingerings of a chord up to a fret, easiest first. Fretted notes stay within
+/// `max_span` frets of each other, at most four fingers are used, no more than two strings are muted
+/// and the chord's lowest note is in the bass
+pub fn find_fingerings(fretboard: &Fretboard, chord: &Chord, max_fret: u32, max_span: u32) -> Vec<Fingering> {
+    let strings = fretboard.get_tuning().get_strings();
+    if strings.is_empty() {
+        return Vec::new();
+    }
+    let chord_notes = chord.get_notes();
+    let bass_note = chord.get_lowest_note();
+    let required = required_tones(chord);
+    // Chords sound on all but two strings, and on three strings at least
+    let min_sounding = strings.len().saturating_sub(2).max(strings.len().min(3));
+    let max_fret = max_fret.min(fretboard.get_frets());
+
+    let mut seen : HashSet<Vec<Option<u32>>> = HashSet::new();
+    let mut res : Vec<Fingering> = Vec::new();
+    for start in 1..=max_fret.max(1) {
+        // Frets each string can play within the window, open strings included
+        let choices : Vec<Vec<Option<u32>>> = (0..strings.len())
+            .map(|string| {
+                let mut frets = vec![ None ];
+                // Positions above the MIDI range are left out
+                frets.extend( std::iter::once(0).chain(start..=(start + max_span).min(max_fret))
+                    .filter(|fret| match fretboard.get_note(&FretPosition::new(string, *fret)) {
+                        Ok( played ) => chord_notes.iter().any(|note| (note.get_index() as i32 - played.get_index() as i32).rem_euclid(12) == 0),
+                        Err( _ ) => false
+                    })
+                    .map(Some) );
+                frets
+            })
+            .collect();
+        let mut frets : Vec<Option<u32>> = vec![ None; strings.len() ];
+        collect_fingerings(0, &choices, &mut frets, &mut |candidate| {
+            if seen.contains(candidate) {
+                return;
+            }
+            let sounding : Vec<MIDINote> = candidate.iter().enumerate()
+                .filter_map(|(string, fret)| fret.and_then(|fret| fretboard.get_note(&FretPosition::new(string, fret)).ok()))
+                .collect();
+            let lowest = match sounding.iter().min() {
+                Some( lowest ) if sounding.len() >= min_sounding => lowest,
+                _ => return
+            };
+            let played : Vec<Note> = sounding.iter().map(|note| note.get_note()).collect();
+            let upper_ok = sounding.iter()
+                .filter(|note| *note != lowest)
+                .all(|note| chord.get_bass_note().map(|bass| bass != note.get_note()).unwrap_or(true));
+            if lowest.get_note() != bass_note || !upper_ok || !required.iter().all(|note| played.contains(note)) {
+                return;
+            }
+            let fingering = Fingering::new(candidate.to_vec());
+            if fingering.get_finger_count() <= MAX_FINGERS && fingering.get_span() <= max_span {
+                seen.insert( candidate.to_vec() );
+                res.push( fingering );
+            }
+        });
+    }
+    res.sort_by(|a, b| a.get_difficulty().partial_cmp(&b.get_difficulty()).unwrap_or(std::cmp::Ordering::Equal)
+        .then(a.get_position().cmp(&b.get_position())));
+    res
+}
+
+/// Calls `f` with every combination of the frets each string can play
+fn collect_fingerings<F: FnMut(&[Option<u32>])>(string: usize, choices: &[Vec<Option<u32>>], frets: &mut Vec<Option<u32>>, f: &mut F) {
+    if string == choices.len() {
+        f(frets);
+        return;
+    }
+    for choice in choices[string].iter() {
+        frets[string] = *choice;
+        collect_fingerings(string + 1, choices, frets, f);
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+use crate::{Note, MIDINote, Chord, Mode, Error};
+use crate::voicing::place;
+
+/// Open string notes of a fretted instrument, strings being ordered from the thickest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tuning {
+    strings: Vec<MIDINote>
+}
+
+impl Tuning {
+
+    /// Creates a tuning from the open string notes, strings being ordered from the thickest
+    pub fn new(strings: Vec<MIDINote>) -> Tuning {
+        Tuning{ strings }
+    }
+
+    /// Creates a tuning from the names of the open string notes e.g. ["E2","A2","D3","G3","B3","E4"]
+    fn from_names(names: &[&str]) -> Tuning {
+        Tuning::new( names.iter().map(|s| s.parse::<MIDINote>().unwrap()).collect() )
+    }
+
+    /// Standard guitar tuning E A D G B E
+    pub fn standard() -> Tuning { Tuning::from_names(&["E2","A2","D3","G3","B3","E4"]) }
+
+    /// Guitar tuning with the lowest string dropped to D
+    pub fn drop_d() -> Tuning { Tuning::from_names(&["D2","A2","D3","G3","B3","E4"]) }
+
+    /// Guitar tuning D A D G A D
+    pub fn dadgad() -> Tuning { Tuning::from_names(&["D2","A2","D3","G3","A3","D4"]) }
+
+    /// Guitar tuning sounding a G major chord on the open strings
+    pub fn open_g() -> Tuning { Tuning::from_names(&["D2","G2","D3","G3","B3","D4"]) }
+
+    /// Seven string guitar tuning with a low B
+    pub fn seven_string() -> Tuning { Tuning::from_names(&["B1","E2","A2","D3","G3","B3","E4"]) }
+
+    /// Four string bass guitar tuning
+    pub fn bass() -> Tuning { Tuning::from_names(&["E1","A1","D2","G2"]) }
+
+    /// Re-entrant ukulele tuning G C E A
+    pub fn ukulele() -> Tuning { Tuning::from_names(&["G4","C4","E4","A4"]) }
+
+    pub fn get_strings(&self) -> Vec<MIDINote> { self.strings.clone() }
+}
+
+impl fmt::Display for Tuning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names : Vec<String> = self.strings.iter().map(|note| note.to_string()).collect();
+        write!(f, "{}", names.join(" "))
+    }
+}
+
+/// A place on the fretboard, strings being counted from the thickest starting at 0 and fret 0
+/// being the open string
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FretPosition {
+    string: usize,
+    fret: u32
+}
+
+impl FretPosition {
+
+    pub fn new(string: usize, fret: u32) -> FretPosition {
+        FretPosition{ string, fret }
+    }
+
+    pub fn get_string(&self) -> usize { self.string }
+    pub fn get_fret(&self) -> u32 { self.fret }
+}
+
+impl fmt::Display for FretPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.string, self.fret)
+    }
+}
+
+/// Represents the neck of a fretted instrument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fretboard {
+    tuning: Tuning,
+    frets: u32
+}
+
+impl Fretboard {
+
+    /// Creates a fretboard with a tuning and a number of frets
+    pub fn new(tuning: Tuning, frets: u32) -> Fretboard {
+        Fretboard{ tuning, frets }
+    }
+
+    pub fn get_tuning(&self) -> Tuning { self.tuning.clone() }
+    pub fn get_frets(&self) -> u32 { self.frets }
+    pub fn get_string_count(&self) -> usize { self.tuning.strings.len() }
+
+    /// Returns the MIDI note sounding at a position
+    pub fn get_note(&self, position: &FretPosition) -> Result<MIDINote, Error> {
+        let open_string = self.get_open_string(position)?;
+        MIDINote::from_midi_number( open_string.get_index() + position.fret )
+    }
+
+    /// Returns every position where a MIDI note can be played
+    pub fn get_positions(&self, note: &MIDINote) -> Vec<FretPosition> {
+        self.tuning.strings.iter().enumerate()
+            .filter(|(_, open_string)| note.get_index() >= open_string.get_index() && note.get_index() - open_string.get_index() <= self.frets)
+            .map(|(string, open_string)| FretPosition::new(string, note.get_index() - open_string.get_index()))
+            .collect()
+    }
+
+    /// Returns every position of a note between two frets included along with the MIDI note played,
+    /// spelled as the note, strings first
+    pub fn find_note(&self, note: &Note, min_fret: u32, max_fret: u32) -> Vec<(FretPosition, MIDINote)> {
+        self.find_notes(&[*note], min_fret, max_fret)
+    }
+
+    /// Returns every position of a chord's notes between two frets included
+    pub fn find_chord(&self, chord: &Chord, min_fret: u32, max_fret: u32) -> Vec<(FretPosition, MIDINote)> {
+        self.find_notes(&chord.get_notes(), min_fret, max_fret)
+    }
+
+    /// Returns every position of a mode's notes between two frets included
+    pub fn find_mode(&self, mode: &Mode, min_fret: u32, max_fret: u32) -> Vec<(FretPosition, MIDINote)> {
+        self.find_notes(&mode.get_notes(true), min_fret, max_fret)
+    }
+
+    /// Returns the open string note of a position's string, failing if the position is off the fretboard
+    fn get_open_string(&self, position: &FretPosition) -> Result<MIDINote, Error> {
+        match self.tuning.strings.get(position.string) {
+            Some(open_string) if position.fret <= self.frets => Ok( *open_string ),
+            _ => Err(Error::InvalidFretPosition { string: position.string, fret: position.fret })
+        }
+    }
+
+    fn find_notes(&self, notes: &[Note], min_fret: u32, max_fret: u32) -> Vec<(FretPosition, MIDINote)> {
+        let mut res = Vec::new();
+        for (string, open_string) in self.tuning.strings.iter().enumerate() {
+            for fret in min_fret..=max_fret.min(self.frets) {
+                let index = (open_string.get_index() + fret) as i32;
+                if let Some(note) = notes.iter().find(|note| (note.get_index() as i32 - index).rem_euclid(12) == 0) {
+                    res.push( (FretPosition::new(string, fret), place(note, index)) );
+                }
+            }
+        }
+        res
+    }
+}
@@ -1,89 +1,234 @@
+use std::cmp::Ordering;
 use std::fmt;
-use crate::Note;
+use std::ops::{Add, Neg, Sub};
+use crate::{Note, NoteLetter, NoteAccidental, MIDINote, Error, SpellingPolicy};
 
-#[derive(Debug, Clone)]
+/// Semitone size of the major or perfect interval for each number of letter steps, which are
+/// also the degrees of the major scale
+pub(crate) const REFERENCE_VALUES : [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+/// Default number of letter steps for each semitone value
+const DEFAULT_STEPS : [i32; 12] = [0, 1, 1, 2, 2, 3, 4, 4, 5, 5, 6, 6];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalQuality {
+    Perfect,
+    Major,
+    Minor,
+    Augmented(u32),
+    Diminished(u32)
+}
+
+impl fmt::Display for IntervalQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Perfect => write!(f, "P"),
+            Self::Major => write!(f, "M"),
+            Self::Minor => write!(f, "m"),
+            Self::Augmented(n) => write!(f, "{}", "A".repeat(*n as usize)),
+            Self::Diminished(n) => write!(f, "{}", "d".repeat(*n as usize))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Interval{
-    value: u32
+    value: i32,
+    steps: i32
 }
 
 /// Represents a musical interval
 impl Interval{
 
-    /// Creates a music interval based on a semitone value
-    pub fn new(value: u32) -> Interval {
-        Interval{value: value}
+    /// Creates a music interval based on a semitone value, negative values being descending
+    pub fn new(value: i32) -> Interval {
+        if value < 0 {
+            let ascending = Interval::new(-value);
+            return Interval{value, steps: -ascending.steps};
+        }
+        let steps = DEFAULT_STEPS[(value % 12) as usize] + 7 * (value / 12);
+        Interval{value, steps}
+    }
+
+    /// Creates a music interval based on a number of letter steps and a semitone value e.g. 3 steps and 6 semitones is A4
+    pub fn from_steps(steps: i32, value: i32) -> Interval {
+        Interval{value, steps}
+    }
+
+    pub fn get_value(&self) -> i32 { self.value }
+    pub fn get_steps(&self) -> i32 { self.steps }
+
+    /// Returns the interval's number regardless of direction e.g. 9 for M9
+    pub fn get_number(&self) -> u32 { self.steps.unsigned_abs() + 1 }
+
+    /// Checks if the interval goes downwards
+    pub fn is_descending(&self) -> bool {
+        self.steps < 0 || (self.steps == 0 && self.value < 0)
     }
 
-    pub fn get_value(&self) -> u32 { self.value }
+    /// Checks if the interval spans more than an octave
+    pub fn is_compound(&self) -> bool { self.steps.abs() > 7 }
+
+    /// Returns the interval's quality e.g. Major for M3
+    pub fn get_quality(&self) -> IntervalQuality {
+        let (steps, value) = if self.is_descending() { (-self.steps, -self.value) } else { (self.steps, self.value) };
+        let simple_steps = steps % 7;
+        let reference = REFERENCE_VALUES[simple_steps as usize] + 12 * (steps / 7);
+        let deviation = value - reference;
+        if is_perfect(simple_steps) {
+            match deviation {
+                0 => IntervalQuality::Perfect,
+                d if d > 0 => IntervalQuality::Augmented(d as u32),
+                d => IntervalQuality::Diminished(-d as u32)
+            }
+        } else {
+            match deviation {
+                0 => IntervalQuality::Major,
+                -1 => IntervalQuality::Minor,
+                d if d > 0 => IntervalQuality::Augmented(d as u32),
+                d => IntervalQuality::Diminished((-d - 1) as u32)
+            }
+        }
+    }
 
     /// Creates a music interval based on two notes
     pub fn from_notes(from: &Note, to: &Note) -> Interval {
-        if  to.get_index() >= from.get_index() {
-            Interval::new(to.get_index() - from.get_index())
-        } else {
-            Interval::new(12 + to.get_index() - from.get_index())
+        let from_letter = from.get_letter();
+        let to_letter = to.get_letter();
+        let mut steps = (to_letter.get_step() as i32 - from_letter.get_step() as i32).rem_euclid(7);
+        let mut value = (to_letter.get_index() as i32 - from_letter.get_index() as i32).rem_euclid(12)
+            + to.get_accidental().get_offset() - from.get_accidental().get_offset();
+        // A lowered unison is measured upwards as a diminished octave
+        if value < 0 {
+            steps += 7;
+            value += 12;
         }
+        Interval::from_steps(steps, value)
     }
 
-    /// Creates a music interval from a name
-    pub fn from_name(s: &str) -> Option<Interval> {
-        for u in 0..11 {
-            let interval = Interval::new(u);
-            if interval.to_string() == s.to_string() {
-                return Some( interval );
-            }
+    /// Creates a music interval between two MIDI notes, descending when the second note is lower
+    pub fn from_midi_notes(from: &MIDINote, to: &MIDINote) -> Interval {
+        let from_step = from.get_note().get_letter().get_step() as i32 + 7 * from.get_octave();
+        let to_step = to.get_note().get_letter().get_step() as i32 + 7 * to.get_octave();
+        Interval::from_steps(to_step - from_step, to.get_index() as i32 - from.get_index() as i32)
+    }
+
+    /// Creates a music interval from a name, a leading minus sign making it descending
+    pub fn from_name(s: &str) -> Result<Interval, Error> {
+        if let Some(ascending_name) = s.strip_prefix('-') {
+            let ascending = Interval::from_name(ascending_name)?;
+            return Ok( -ascending );
         }
-        None
+        let unknown = || Error::UnknownName { kind: "Interval", name: s.to_string() };
+        let split_idx = s.find(|c: char| c.is_ascii_digit()).ok_or_else(unknown)?;
+        let (quality, number) = s.split_at(split_idx);
+        let steps = match number.parse::<i32>() {
+            Ok(number) if number > 0 => number - 1,
+            _ => return Err(unknown())
+        };
+        let simple_steps = steps % 7;
+        let reference = REFERENCE_VALUES[simple_steps as usize] + 12 * (steps / 7);
+        let deviation = match quality {
+            "P" if is_perfect(simple_steps) => 0,
+            "M" if !is_perfect(simple_steps) => 0,
+            "m" if !is_perfect(simple_steps) => -1,
+            q if !q.is_empty() && q.chars().all(|c| c == 'A') => q.len() as i32,
+            q if !q.is_empty() && q.chars().all(|c| c == 'd') => {
+                if is_perfect(simple_steps) { -(q.len() as i32) } else { -(q.len() as i32) - 1 }
+            },
+            _ => return Err(unknown())
+        };
+        let value = reference + deviation;
+        if value < 0 {
+            return Err(unknown());
+        }
+        Ok( Interval::from_steps(steps, value) )
     }
 
-    /// Apply a music interval to a note and return note
-    pub fn apply(&self, obj: &Note) -> Note {
-        let mut res = obj.clone();
-        for _ in 0..self.value {
-            res = res.next();
+    /// Returns the interval reduced to within an octave e.g. M9 becomes M2, octaves being kept as P8
+    pub fn simple(&self) -> Interval {
+        let octaves = (self.steps.abs() - 1).max(0) / 7;
+        let sign = if self.is_descending() { -1 } else { 1 };
+        Interval::from_steps(self.steps - sign * 7 * octaves, self.value - sign * 12 * octaves)
+    }
+
+    /// Returns the inversion of the interval within an octave e.g. M3 becomes m6
+    pub fn invert(&self) -> Interval {
+        let simple = self.simple();
+        let sign = if self.is_descending() { -1 } else { 1 };
+        Interval::from_steps(sign * (7 - sign * simple.steps), sign * (12 - sign * simple.value))
+    }
+
+    /// Apply a music interval to a note and return the correctly spelled note, failing when
+    /// the spelling would need more than a double accidental
+    pub fn try_apply(&self, obj: &Note) -> Result<Note, Error> {
+        let letter = NoteLetter::from_step( (obj.get_letter().get_step() as i32 + self.steps).rem_euclid(7) as u32 );
+        let target_idx = (obj.get_index() as i32 + self.value).rem_euclid(12);
+        let offset = (target_idx - letter.get_index() as i32 + 6).rem_euclid(12) - 6;
+        match NoteAccidental::from_offset(offset) {
+            Some(accidental) => Ok( Note::new(letter, accidental) ),
+            None => Err(Error::UnspellableNote { letter, offset })
         }
-        res
     }
+
+    /// Apply a music interval to a note and return the correctly spelled note
+    pub fn apply(&self, obj: &Note) -> Note {
+        self.try_apply(obj).unwrap_or_else(|_| {
+            // Spelling would need more than a double accidental so walk the semitones instead
+            let mut res = *obj;
+            for _ in 0..self.value.abs() {
+                res = if self.value > 0 { res.next() } else { res.previous() };
+            }
+            res
+        })
+    }
+
+    /// Apply a music interval to a note and return the note spelled following a policy
+    pub fn apply_with(&self, obj: &Note, policy: &SpellingPolicy) -> Note {
+        policy.spell( &self.apply(obj) )
+    }
+}
+
+fn is_perfect(simple_steps: i32) -> bool {
+    matches!(simple_steps, 0 | 3 | 4)
 }
 
 impl fmt::Display for Interval {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let res = match self.value % 12 {
-            0 => String::from("P"),
-            1 => String::from("m"),
-            2 => String::from("M"),
-            3 => String::from("m"),
-            4 => String::from("M"),
-            5 => String::from("P"),
-            6 => String::from("d"),
-            7 => String::from("P"),
-            8 => String::from("m"),
-            9 => String::from("M"),
-            10 => String::from("m"),
-            _ => String::from("M")
-        };
-        let number = match self.value % 12 {
-            0 => 1,
-            1 => 2,
-            2 => 2,
-            3 => 3,
-            4 => 3,
-            5 => 4,
-            6 => 5,
-            7 => 5,
-            8 => 6,
-            9 => 6,
-            10 => 7,
-            _ => 7
-        };
-        write!(f, "{}{}", res, number)
+        let direction = if self.is_descending() { "-" } else { "" };
+        write!(f, "{}{}{}", direction, self.get_quality(), self.get_number())
     }
 }
 
-impl PartialEq for Interval {
-    fn eq(&self, other: &Interval) -> bool {
-        self.value == other.value
+impl Ord for Interval {
+    fn cmp(&self, other: &Interval) -> Ordering {
+        self.value.cmp(&other.value).then(self.steps.cmp(&other.steps))
     }
 }
 
-impl Eq for Interval {}
\ No newline at end of file
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Interval) -> Option<Ordering> {
+        Some( self.cmp(other) )
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    fn add(self, other: Interval) -> Interval {
+        Interval::from_steps(self.steps + other.steps, self.value + other.value)
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    fn sub(self, other: Interval) -> Interval {
+        self + (-other)
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+    fn neg(self) -> Interval {
+        Interval::from_steps(-self.steps, -self.value)
+    }
+}
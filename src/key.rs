@@ -0,0 +1,150 @@
+use std::fmt;
+use crate::{Note, NoteLetter, NoteAccidental, Interval, Mode, Scale, Error, next_note_letter, fifths_position};
+use crate::interval::REFERENCE_VALUES;
+
+/// Letters in the order sharps are added to a key signature, which is also their order on the line of fifths
+pub(crate) const SHARP_ORDER : [NoteLetter; 7] = [NoteLetter::F, NoteLetter::C, NoteLetter::G, NoteLetter::D, NoteLetter::A, NoteLetter::E, NoteLetter::B];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum KeyQuality {
+    Major,
+    Minor
+}
+
+impl fmt::Display for KeyQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Major => write!(f, "major"),
+            Self::Minor => write!(f, "minor")
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Key {
+    tonic: Note,
+    quality: KeyQuality
+}
+
+/// Represents a major or minor key
+impl Key {
+
+    /// Creates a key from its tonic and quality
+    pub fn new(tonic: Note, quality: KeyQuality) -> Key {
+        Key{ tonic, quality }
+    }
+
+    /// Creates the key whose signature a mode is written with e.g. D Dorian is written in C major
+    pub fn from_mode(mode: &Mode) -> Result<Key, Error> {
+        match (mode.get_scale(), mode.get_degree()) {
+            (Scale::Major, 5) | (Scale::MelodicMinor, 0) | (Scale::HarmonicMinor, 0) => Ok( Key::new(mode.get_root(), KeyQuality::Minor) ),
+            (Scale::Major, degree) if degree < 7 => {
+                let degree_interval = Interval::from_steps(degree as i32, REFERENCE_VALUES[degree]);
+                Ok( Key::new(mode.get_root() - degree_interval, KeyQuality::Major) )
+            },
+            _ => Err(Error::NoKeySignature(mode.to_string()))
+        }
+    }
+
+    pub fn get_tonic(&self) -> Note { self.tonic }
+    pub fn get_quality(&self) -> KeyQuality { self.quality }
+
+    /// Returns the number of sharps in the key signature, flats being counted as negative
+    pub fn get_signature(&self) -> i32 {
+        match self.quality {
+            KeyQuality::Major => fifths_position(&self.tonic),
+            KeyQuality::Minor => fifths_position(&self.tonic) - 3
+        }
+    }
+
+    /// Returns the sharps or flats of the key signature in the order they are written
+    pub fn get_accidentals(&self) -> Vec<Note> {
+        let signature = self.get_signature();
+        (0..signature.abs())
+            .map(|i| {
+                if signature > 0 {
+                    let accidental = NoteAccidental::from_offset(1 + i / 7).unwrap_or(NoteAccidental::DoubleSharp);
+                    Note::new(SHARP_ORDER[(i % 7) as usize], accidental)
+                } else {
+                    let accidental = NoteAccidental::from_offset(-1 - i / 7).unwrap_or(NoteAccidental::DoubleFlat);
+                    Note::new(SHARP_ORDER[(6 - i % 7) as usize], accidental)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the notes of the key's major or natural minor scale starting from the tonic
+    pub fn get_notes(&self) -> Vec<Note> {
+        let signature = self.get_signature();
+        let mut letter = self.tonic.get_letter();
+        let mut res = Vec::new();
+        for _ in 0..7 {
+            let accidental = NoteAccidental::from_offset( signature_offset(letter, signature) )
+                .unwrap_or(NoteAccidental::Natural);
+            res.push( Note::new(letter, accidental) );
+            letter = next_note_letter(&letter);
+        }
+        res
+    }
+
+    /// Returns the relative key sharing the same signature e.g. A minor for C major
+    pub fn relative(&self) -> Key {
+        let minor_third = Interval::from_steps(2, 3);
+        match self.quality {
+            KeyQuality::Major => Key::new(self.tonic - minor_third, KeyQuality::Minor),
+            KeyQuality::Minor => Key::new(self.tonic + minor_third, KeyQuality::Major)
+        }
+    }
+
+    /// Returns the parallel key sharing the same tonic e.g. C minor for C major
+    pub fn parallel(&self) -> Key {
+        match self.quality {
+            KeyQuality::Major => Key::new(self.tonic, KeyQuality::Minor),
+            KeyQuality::Minor => Key::new(self.tonic, KeyQuality::Major)
+        }
+    }
+
+    /// Spells a note as it would be written in the key. Diatonic notes take the key's spelling,
+    /// the raised sixth and seventh of minor keys are written as such, and other chromatic notes
+    /// are written as raised in sharp keys and as lowered in flat keys
+    pub fn spell(&self, note: &Note) -> Note {
+        let notes = self.get_notes();
+        if let Some(diatonic) = notes.iter().find(|&elt| elt == note) {
+            return *diatonic;
+        }
+        let from_tonic = Interval::from_notes(&self.tonic, note).get_value() % 12;
+        if self.quality == KeyQuality::Minor && (from_tonic == 9 || from_tonic == 11) {
+            let raised_degree = if from_tonic == 9 { notes[5] } else { notes[6] };
+            if let Ok(spelled) = note.respell(raised_degree.get_letter()) {
+                return spelled;
+            }
+        }
+        let neighbours = [note.previous(), note.next()];
+        let candidates = if self.get_signature() >= 0 { [neighbours[0], neighbours[1]] } else { [neighbours[1], neighbours[0]] };
+        for candidate in candidates.iter() {
+            if let Some(diatonic) = notes.iter().find(|&elt| elt == candidate) {
+                if let Ok(spelled) = note.respell(diatonic.get_letter()) {
+                    return spelled;
+                }
+            }
+        }
+        *note
+    }
+}
+
+/// Returns the accidental offset a key signature applies to a letter
+fn signature_offset(letter: NoteLetter, signature: i32) -> i32 {
+    let position = SHARP_ORDER.iter().position(|&elt| elt == letter).unwrap_or(0) as i32;
+    let count = |n: i32, p: i32| if n > p { (n - p - 1) / 7 + 1 } else { 0 };
+    if signature >= 0 {
+        count(signature, position)
+    } else {
+        -count(-signature, 6 - position)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.tonic, self.quality)
+    }
+}
@@ -3,9 +3,33 @@ mod midinote;
 mod interval;
 mod mode;
 mod chord;
+mod error;
+mod key;
+mod circle;
+mod spelling;
+mod recognition;
+mod voicing;
+mod voice_leading;
+mod fretboard;
+mod fingering;
+mod diagram;
+mod tab;
+mod smf;
 
-pub use note::{Note, NoteAccidental, NoteLetter, next_note_letter, all_note_letters, all_note_accidentals};
+pub use note::{Note, NoteAccidental, NoteLetter, ParseNoteError, next_note_letter, all_note_letters, all_note_accidentals};
 pub use midinote::MIDINote;
-pub use chord::{ChordQuality, Chord, ChordPosition};
+pub use chord::{ChordQuality, Chord, ChordPosition, ChordModifier, all_chord_qualities};
 pub use mode::{Scale, Mode, get_mode_names};
-pub use interval::Interval;
\ No newline at end of file
+pub use interval::{Interval, IntervalQuality};
+pub use error::Error;
+pub use key::{Key, KeyQuality};
+pub use circle::{fifths_position, circle_position, key_from_signature, step_clockwise, step_counter_clockwise, circle_distance, closely_related_keys, enharmonic_key};
+pub use spelling::SpellingPolicy;
+pub use recognition::{ChordMatch, VoicingMatch, recognise_chords, recognise_voicing};
+pub use voicing::{VoicingStyle, all_voicing_styles, voice_chord};
+pub use voice_leading::{chord_voicings, voice_lead, voice_lead_progression, voice_leading_distance};
+pub use fretboard::{Tuning, FretPosition, Fretboard};
+pub use fingering::{Barre, Fingering, find_fingerings};
+pub use diagram::{chord_box, fretboard_svg, keyboard_svg, keyboard_svg_for_notes};
+pub use tab::{TabNote, parse_tab, write_tab, render_tab, render_melody_tab};
+pub use smf::{NoteEvent, ProgramChange, Tempo, TimeSignature, KeySignature, MetaEvent, Track, MidiFile, read_smf, write_smf, notes_from_voicings};
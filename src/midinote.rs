@@ -1,8 +1,12 @@
 use std::cmp::Ordering;
 use std::fmt;
-use crate::{Note, NoteLetter, NoteAccidental};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+use crate::{Note, NoteLetter, NoteAccidental, Interval, ParseNoteError, Error};
+use crate::note::parse_note_prefix;
 
-#[derive(Debug, Clone, Copy, Hash)]
+#[derive(Debug, Clone, Copy)]
 pub struct MIDINote{
     note: Note,
     octave: i32
@@ -11,134 +15,151 @@ pub struct MIDINote{
 /// Represents a MIDI note
 impl MIDINote{
 
-    /// Creates a new music note
-    pub fn new(letter: NoteLetter, accidental: NoteAccidental, octave: i32) -> MIDINote {
-        let note = Note::new(letter, accidental);
-        MIDINote{note: note, octave: octave}
+    /// Creates a new music note, failing below C-1 where notes have no index
+    pub fn new(letter: NoteLetter, accidental: NoteAccidental, octave: i32) -> Result<MIDINote, Error> {
+        MIDINote::from_note(Note::new(letter, accidental), octave)
     }
 
-    /// Creates a new MIDI note
-    pub fn from_note(note: Note, octave: i32) -> MIDINote {
-        MIDINote{note: note, octave: octave}
+    /// Creates a new MIDI note, failing below C-1 where notes have no index
+    pub fn from_note(note: Note, octave: i32) -> Result<MIDINote, Error> {
+        let index = note.get_offset_index() + 12 * (octave + 1);
+        if index >= 0 {
+            Ok( MIDINote{note, octave} )
+        } else {
+            Err( Error::MidiNumberOutOfRange(index as i64) )
+        }
     }
 
-    // Creates a new music note based on a MIDI number
-    pub fn from_midi_number(midi_num: u32) -> Option<MIDINote> {
+    pub fn get_note(&self) -> Note { self.note }
+    pub fn get_octave(&self) -> i32 { self.octave }
+
+    /// Creates a new music note based on a MIDI number
+    pub fn from_midi_number(midi_num: u32) -> Result<MIDINote, Error> {
         if midi_num < 128 {
-            let mut midi_note = MIDINote::new(NoteLetter::C, NoteAccidental::Natural, -2);
+            let mut midi_note = MIDINote{ note: Note::new(NoteLetter::C, NoteAccidental::Natural), octave: -1 };
             for _ in 0..midi_num {
                 midi_note = midi_note.next();
             }
-            Some( midi_note )
+            Ok( midi_note )
         } else {
-            None
+            Err( Error::MidiNumberOutOfRange(midi_num as i64) )
         }
     }
     
-    /// Returns the note's numerical id
+    /// Returns the note's numerical id, C-1 being 0
     pub fn get_index(&self) -> u32 {
-        let mut res : u32 = match self.note.get_letter() {
-            NoteLetter::C => 0,
-            NoteLetter::D => 2,
-            NoteLetter::E => 4,
-            NoteLetter::F => 5,
-            NoteLetter::G => 7,
-            NoteLetter::A => 9,
-            NoteLetter::B => 11
-        };
-
-        res = match self.note.get_accidental() {
-            NoteAccidental::Flat => res - 1,
-            NoteAccidental::Sharp => res + 1,
-            _ => res
-        };
-
-        res += 12 * (self.octave as u32 + 1); 
-        
-        res
+        (self.note.get_offset_index() + 12 * (self.octave + 1)) as u32
     }
 
     /// Returns the note's MIDI representation 
-    pub fn get_midi_number(&self) -> Option<u32> {
-        let idx = self.get_index();
-        if idx < 128 {
-            Some( idx as u32 )
+    pub fn get_midi_number(&self) -> Result<u32, Error> {
+        let idx = self.get_index() as i32;
+        if (0..128).contains(&idx) {
+            Ok( idx as u32 )
         } else {
-            None
+            Err( Error::MidiNumberOutOfRange(idx as i64) )
         }
     }
 
     /// Returns the note's enharmonic equivalent
     pub fn equivalents(&self) -> Vec<MIDINote> {
         self.note.equivalents().into_iter()
-            .map(|note| MIDINote::from_note(note, self.octave))
+            .filter_map(|note| MIDINote::from_note(note, self.octave).ok())
             .collect()
     }
 
     /// Returns the note one semitone above
     pub fn next(&self) -> MIDINote {
-        match self.note.get_accidental() {
+        let (letter, accidental, octave) = match self.note.get_accidental() {
             NoteAccidental::Natural => {
                 match self.note.get_letter() {
-                    NoteLetter::B  => MIDINote::new(NoteLetter::C, NoteAccidental::Natural, self.octave + 1),
-                    NoteLetter::E  => MIDINote::new(NoteLetter::F, NoteAccidental::Natural, self.octave),
-                    _ =>  MIDINote::new(self.note.get_letter(), NoteAccidental::Sharp, self.octave)
+                    NoteLetter::B  => (NoteLetter::C, NoteAccidental::Natural, self.octave + 1),
+                    NoteLetter::E  => (NoteLetter::F, NoteAccidental::Natural, self.octave),
+                    _ =>  (self.note.get_letter(), NoteAccidental::Sharp, self.octave)
                 }
             },
-            NoteAccidental::Flat => {
+            NoteAccidental::Flat => (self.note.get_letter(), NoteAccidental::Natural, self.octave),
+            NoteAccidental::DoubleFlat => (self.note.get_letter(), NoteAccidental::Flat, self.octave),
+            NoteAccidental::Sharp => {
                 match self.note.get_letter() {
-                    NoteLetter::C => MIDINote::new(NoteLetter::C, NoteAccidental::Natural, self.octave + 1),
-                    _ => MIDINote::new(self.note.get_letter(), NoteAccidental::Natural, self.octave)
+                    NoteLetter::C => (NoteLetter::D, NoteAccidental::Natural, self.octave),
+                    NoteLetter::D => (NoteLetter::E, NoteAccidental::Natural, self.octave),
+                    NoteLetter::E => (NoteLetter::F, NoteAccidental::Sharp, self.octave),
+                    NoteLetter::F => (NoteLetter::G, NoteAccidental::Natural, self.octave),
+                    NoteLetter::G => (NoteLetter::A, NoteAccidental::Natural, self.octave),
+                    NoteLetter::A => (NoteLetter::B, NoteAccidental::Natural, self.octave),
+                    NoteLetter::B => (NoteLetter::C, NoteAccidental::Sharp, self.octave + 1)
                 }
             },
-            NoteAccidental::Sharp => {
+            NoteAccidental::DoubleSharp => {
                 match self.note.get_letter() {
-                    NoteLetter::C => MIDINote::new(NoteLetter::D, NoteAccidental::Natural, self.octave),
-                    NoteLetter::D => MIDINote::new(NoteLetter::E, NoteAccidental::Natural, self.octave),
-                    NoteLetter::E => MIDINote::new(NoteLetter::F, NoteAccidental::Sharp, self.octave),
-                    NoteLetter::F => MIDINote::new(NoteLetter::G, NoteAccidental::Natural, self.octave),
-                    NoteLetter::G => MIDINote::new(NoteLetter::A, NoteAccidental::Natural, self.octave),
-                    NoteLetter::A => MIDINote::new(NoteLetter::B, NoteAccidental::Natural, self.octave),
-                    NoteLetter::B => MIDINote::new(NoteLetter::C, NoteAccidental::Sharp, self.octave)
+                    NoteLetter::C => (NoteLetter::D, NoteAccidental::Sharp, self.octave),
+                    NoteLetter::D => (NoteLetter::F, NoteAccidental::Natural, self.octave),
+                    NoteLetter::E => (NoteLetter::G, NoteAccidental::Natural, self.octave),
+                    NoteLetter::F => (NoteLetter::G, NoteAccidental::Sharp, self.octave),
+                    NoteLetter::G => (NoteLetter::A, NoteAccidental::Sharp, self.octave),
+                    NoteLetter::A => (NoteLetter::C, NoteAccidental::Natural, self.octave + 1),
+                    NoteLetter::B => (NoteLetter::D, NoteAccidental::Natural, self.octave + 1)
                 }
-            },
-        }
+            }
+        };
+        // The note above a note from C-1 up is never below C-1
+        MIDINote{ note: Note::new(letter, accidental), octave }
     }
 
-    /// Returns the note one semitone below
-    pub fn previous(&self) -> MIDINote {
-        match self.note.get_accidental() {
+    /// Returns the note one semitone below, failing below C-1
+    pub fn previous(&self) -> Result<MIDINote, Error> {
+        let (letter, accidental, octave) = match self.note.get_accidental() {
             NoteAccidental::Natural => {
                 match self.note.get_letter() {
-                    NoteLetter::C  => MIDINote::new(NoteLetter::B, NoteAccidental::Natural, self.octave - 1),
-                    NoteLetter::F  => MIDINote::new(NoteLetter::E, NoteAccidental::Natural, self.octave),
-                    _ => MIDINote::new(self.note.get_letter(), NoteAccidental::Flat, self.octave)
+                    NoteLetter::C  => (NoteLetter::B, NoteAccidental::Natural, self.octave - 1),
+                    NoteLetter::F  => (NoteLetter::E, NoteAccidental::Natural, self.octave),
+                    _ => (self.note.get_letter(), NoteAccidental::Flat, self.octave)
                 }
             },
-            NoteAccidental::Sharp => {
+            NoteAccidental::Sharp => (self.note.get_letter(), NoteAccidental::Natural, self.octave),
+            NoteAccidental::DoubleSharp => (self.note.get_letter(), NoteAccidental::Sharp, self.octave),
+            NoteAccidental::Flat => {
                 match self.note.get_letter() {
-                    NoteLetter::B => MIDINote::new(NoteLetter::B, NoteAccidental::Natural, self.octave - 1),
-                    _ => MIDINote::new(self.note.get_letter(), NoteAccidental::Natural, self.octave)
+                    NoteLetter::C => (NoteLetter::B, NoteAccidental::Flat, self.octave - 1),
+                    NoteLetter::D => (NoteLetter::C, NoteAccidental::Natural, self.octave),
+                    NoteLetter::E => (NoteLetter::D, NoteAccidental::Natural, self.octave),
+                    NoteLetter::F => (NoteLetter::E, NoteAccidental::Flat, self.octave),
+                    NoteLetter::G => (NoteLetter::F, NoteAccidental::Natural, self.octave),
+                    NoteLetter::A => (NoteLetter::G, NoteAccidental::Natural, self.octave),
+                    NoteLetter::B => (NoteLetter::A, NoteAccidental::Natural, self.octave)
                 }
             },
-            NoteAccidental::Flat => {
+            NoteAccidental::DoubleFlat => {
                 match self.note.get_letter() {
-                    NoteLetter::C => MIDINote::new(NoteLetter::B, NoteAccidental::Flat, self.octave),
-                    NoteLetter::D => MIDINote::new(NoteLetter::C, NoteAccidental::Natural, self.octave),
-                    NoteLetter::E => MIDINote::new(NoteLetter::D, NoteAccidental::Natural, self.octave),
-                    NoteLetter::F => MIDINote::new(NoteLetter::E, NoteAccidental::Flat, self.octave),
-                    NoteLetter::G => MIDINote::new(NoteLetter::F, NoteAccidental::Natural, self.octave),
-                    NoteLetter::A => MIDINote::new(NoteLetter::G, NoteAccidental::Natural, self.octave),
-                    NoteLetter::B => MIDINote::new(NoteLetter::A, NoteAccidental::Natural, self.octave)
+                    NoteLetter::C => (NoteLetter::A, NoteAccidental::Natural, self.octave - 1),
+                    NoteLetter::D => (NoteLetter::B, NoteAccidental::Natural, self.octave - 1),
+                    NoteLetter::E => (NoteLetter::D, NoteAccidental::Flat, self.octave),
+                    NoteLetter::F => (NoteLetter::D, NoteAccidental::Natural, self.octave),
+                    NoteLetter::G => (NoteLetter::E, NoteAccidental::Natural, self.octave),
+                    NoteLetter::A => (NoteLetter::G, NoteAccidental::Flat, self.octave),
+                    NoteLetter::B => (NoteLetter::A, NoteAccidental::Flat, self.octave)
                 }
-            },
-        }
+            }
+        };
+        MIDINote::new(letter, accidental, octave)
     }
 }
 
 impl fmt::Display for MIDINote {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.note.to_string(), self.octave)
+        write!(f, "{}{}", self.note, self.octave)
+    }
+}
+
+impl FromStr for MIDINote {
+    type Err = ParseNoteError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (note, octave) = parse_note_prefix(s)?;
+        match octave.parse::<i32>() {
+            Ok(number) => MIDINote::from_note(note, number).map_err(|_| ParseNoteError::OutOfRange(s.trim().to_string())),
+            Err(_) => Err(ParseNoteError::InvalidOctave(octave.to_string()))
+        }
     }
 }
 
@@ -150,7 +171,7 @@ impl Ord for MIDINote {
 
 impl PartialOrd for MIDINote {
     fn partial_cmp(&self, other: &MIDINote) -> Option<Ordering> {
-        Some( self.get_index().cmp(&other.get_index()) )
+        Some( self.cmp(other) )
     }
 }
 
@@ -160,4 +181,27 @@ impl PartialEq for MIDINote {
     }
 }
 
-impl Eq for MIDINote {}
\ No newline at end of file
+impl Eq for MIDINote {}
+
+impl Hash for MIDINote {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get_index().hash(state);
+    }
+}
+
+/// Adding an interval fails when the note would be below C-1
+impl Add<Interval> for MIDINote {
+    type Output = Result<MIDINote, Error>;
+    fn add(self, interval: Interval) -> Result<MIDINote, Error> {
+        let note = interval.apply(&self.note);
+        let target_idx = self.get_index() as i32 + interval.get_value();
+        MIDINote::from_note(note, (target_idx - note.get_offset_index()).div_euclid(12) - 1)
+    }
+}
+
+impl Sub<Interval> for MIDINote {
+    type Output = Result<MIDINote, Error>;
+    fn sub(self, interval: Interval) -> Result<MIDINote, Error> {
+        self + (-interval)
+    }
+}
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::fmt;
 use std::convert::TryFrom;
-use crate::{Note, Interval, Chord, next_note_letter};
+use crate::{Note, Interval, Chord, Error, SpellingPolicy, next_note_letter};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Scale{
@@ -28,7 +28,7 @@ pub fn get_mode_names(scale: &Scale) -> Vec<&'static str> {
 }
 
 impl TryFrom<&str> for Scale {
-    type Error = &'static str;
+    type Error = Error;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "Major" => Ok( Self::Major ),
@@ -39,7 +39,7 @@ impl TryFrom<&str> for Scale {
             "Diminished" => Ok( Self::Diminished ),
             "WholeTone" => Ok( Self::WholeTone ),
             "Chromatic" => Ok( Self::Chromatic ),
-            _ => Err("No Scale found by this name")
+            _ => Err(Error::UnknownName { kind: "Scale", name: value.to_string() })
         }
     }
 }
@@ -84,9 +84,13 @@ impl Mode {
     
     /// Creates a Scale based on names and intervals
     pub fn new(root_note: Note, scale: Scale, degree: usize ) -> Self {
-        Mode{ root_note: root_note, scale: scale, degree: degree }
+        Mode{ root_note, scale, degree }
     }
 
+    pub fn get_root(&self) -> Note { self.root_note }
+    pub fn get_scale(&self) -> Scale { self.scale.clone() }
+    pub fn get_degree(&self) -> usize { self.degree }
+
     /// Get note intervals
     pub fn get_intervals(&self) -> Vec<Interval> {
         let interval_names : Vec<&str> = match self.scale {
@@ -94,10 +98,10 @@ impl Mode {
             Scale::MelodicMinor => vec!["M2","m2","M2","M2","M2","M2","m2"],
             Scale::HarmonicMinor => vec!["M2","m2","M2","M2","m2","m3","m2"],
             Scale::Diminished => vec!["M2","m2","M2","m2","M2","m2","M2","m2"],
-            Scale::WholeTone => std::iter::repeat("M2").take(6).collect(),
+            Scale::WholeTone => vec!["M2"; 6],
             Scale::HarmonicMajor => vec!["M2","M2","m2","M2","m2","m3","m2"],
             Scale::DoubleHarmonic => vec!["m2","m3","m2","M2","m2","m3","m2"],
-            Scale::Chromatic => std::iter::repeat("m2").take(10).collect()
+            Scale::Chromatic => vec!["m2"; 10]
         };
         let intervals : Vec<Interval> = interval_names.iter()
             .cycle()
@@ -109,13 +113,13 @@ impl Mode {
     }
 
     /// Find Scales containing all notes provided
-    pub fn identify(notes: &Vec<Note>) -> Vec<Self> {
+    pub fn identify(notes: &[Note]) -> Vec<Self> {
         let mut res : Vec<Self> = Vec::new();
-        for scale in all_scales().into_iter() {
+        for scale in all_scales() {
             let num_modes = get_mode_names( &scale ).len();
             for degree in 0..num_modes {
                 for note in notes.iter().skip(1) {
-                    let mode = Self::new(note.clone(), scale.clone(), degree);
+                    let mode = Self::new(*note, scale.clone(), degree);
                     if mode.get_notes(false).iter().zip( notes.iter() ).all(|elt| elt.0 == elt.1) {
                         res.push( mode );
                     }
@@ -125,58 +129,60 @@ impl Mode {
         res
     }
 
-    /// Prints the numeral of the Chord in the Scale if it exists
-    pub fn chord_numeral(&self, chord: &Chord) -> Option<String> {
-        if self.contains_notes( &chord.get_notes() ) {
-            let index = self.get_note_index( &chord.get_root() ).unwrap();
-            return Some( chord.as_numeral( &self.root_note, index ) );
+    /// Prints the numeral of the Chord in the Scale, failing if the chord is not part of it
+    pub fn chord_numeral(&self, chord: &Chord) -> Result<String, Error> {
+        let not_in_mode = || Error::ChordNotInMode { chord: chord.to_string(), mode: self.to_string() };
+        if !self.contains_notes( &chord.get_notes() ) {
+            return Err( not_in_mode() );
         }
-        None
+        let index = self.get_note_index( &chord.get_root() ).ok_or_else(not_in_mode)?;
+        chord.as_numeral( &self.root_note, index )
     }
 
     /// Get the notes of the scale
     pub fn get_notes(&self, optimise: bool) -> Vec<Note> {
         let intervals = self.get_intervals(); 
-        let mut res = vec![ self.root_note.clone() ];
+        let mut res = vec![ self.root_note ];
         if optimise {
             let mut target_note_letter = next_note_letter( &self.root_note.get_letter() );
-            for i in 0..intervals.len() {
-                let note = intervals[i].apply( &res[i] );
-                if target_note_letter == note.get_letter() {
-                    res.push( note );
-                    target_note_letter = next_note_letter( &note.get_letter() );
-                } else {
-                    for eq_note in note.equivalents().into_iter() {
-                        if target_note_letter == eq_note.get_letter() {
-                            res.push( eq_note );
-                            target_note_letter = next_note_letter( &eq_note.get_letter() );
-                            break;
-                        }
-                    }
-                }
+            for (i, interval) in intervals.iter().enumerate() {
+                let note = interval.apply( &res[i] );
+                // Falls back to the unspelled note when the target letter cannot be used
+                let spelled_note = note.respell(target_note_letter).unwrap_or(note);
+                res.push( spelled_note );
+                target_note_letter = next_note_letter( &spelled_note.get_letter() );
             }
         } else {
-            for i in 0..intervals.len() {
-                let note = intervals[i].apply( &res[i] );
+            for (i, interval) in intervals.iter().enumerate() {
+                let note = (0..interval.get_value()).fold(res[i], |note, _| note.next());
                 res.push( note );
             }
         }
         res
     }
 
+    /// Get the notes of the scale spelled following a policy
+    pub fn get_notes_with(&self, policy: &SpellingPolicy) -> Vec<Note> {
+        self.get_notes(true).iter()
+            .map(|note| policy.spell(note) )
+            .collect()
+    }
+
     /// Get scale Intervals from root note
     pub fn get_root_intervals(&self) -> Vec<Interval> {
         let mut root_intervals : Vec<Interval> = self.get_intervals().iter()
             .scan(0, |state, x| {
-                *state = *state + x.get_value();
+                *state += x.get_value();
                 Some( Interval::new(*state) )
             }).collect();
         root_intervals.insert(0, Interval::new(0));
         root_intervals
     }
 
-    /// Get the chords of the scale
-    pub fn get_chords(&self, optimise: bool, extended: bool) -> Vec<Option<Chord>> {
+    /// Get the chords of the scale by stacking thirds up to `depth` notes e.g. 3 for triads,
+    /// 4 for sevenths and up to 7 for thirteenths. Stacking stops early if a note repeats and
+    /// the depth is kept within 3 to 7
+    pub fn get_chords(&self, optimise: bool, depth: usize) -> Vec<Option<Chord>> {
         let notes = self.get_notes(optimise);
         let notes_cycle : Vec<Note> = notes.iter()
             .cycle()
@@ -185,36 +191,24 @@ impl Mode {
             .collect();
         let mut res = Vec::<Option<Chord>>::new();
         for i in 0..notes.len() {
-            let first_note = notes_cycle[i].clone();
-            let second_note = notes_cycle[i + 2].clone();
-            let third_note = notes_cycle[i + 4].clone();
-            if !extended {
-                let triads = Chord::identify( &vec![first_note, second_note, third_note] );
-                if triads.len() > 0 {
-                    res.push( Some(triads[0].clone()) );
-                } else {
-                    res.push( None );
-                }
-            } else {
-                let fourth_note = notes_cycle[i + 6].clone();
-
-                let chords = if vec![first_note, second_note, third_note].iter().find(|&&note| note == fourth_note).is_none() {
-                    Chord::identify( &vec![first_note, second_note, third_note, fourth_note] )
-                } else {
-                    Chord::identify( &vec![first_note, second_note, third_note] )
-                };
-                if chords.len() > 0 {
-                    res.push( Some(chords[0].clone()) );
-                } else {
-                    res.push( None );
+            let mut stack : Vec<Note> = Vec::new();
+            for k in 0..depth.clamp(3, 7) {
+                let note = notes_cycle[i + 2 * k];
+                if stack.contains(&note) {
+                    break;
                 }
+                stack.push( note );
             }
+            // Slash chord readings do not stack thirds from the degree
+            let chord = Chord::identify( &stack ).into_iter()
+                .find(|chord| chord.get_bass_note().is_none());
+            res.push( chord );
         }
         res
     }
 
     /// Checks if Scale contains all Notes provided
-    pub fn contains_notes(&self, notes: &Vec<Note>) -> bool {
+    pub fn contains_notes(&self, notes: &[Note]) -> bool {
         notes.iter().all(|elt1| {
             self.get_notes(false).iter().any(|elt2| elt2 == elt1)
         })
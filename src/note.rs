@@ -1,14 +1,58 @@
 use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::convert::TryFrom;
+use std::str::FromStr;
+use std::ops::{Add, Sub};
 use itertools::Itertools;
-use crate::Interval;
+use crate::{Interval, Error, SpellingPolicy};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 pub enum NoteLetter{ A,B,C,D,E,F,G }
 
+impl NoteLetter {
+    /// Returns the numerical index of the natural note with this letter
+    pub fn get_index(&self) -> u32 {
+        match self {
+            NoteLetter::C => 0,
+            NoteLetter::D => 2,
+            NoteLetter::E => 4,
+            NoteLetter::F => 5,
+            NoteLetter::G => 7,
+            NoteLetter::A => 9,
+            NoteLetter::B => 11
+        }
+    }
+
+    /// Returns the letter's position counted in steps from C
+    pub fn get_step(&self) -> u32 {
+        match self {
+            NoteLetter::C => 0,
+            NoteLetter::D => 1,
+            NoteLetter::E => 2,
+            NoteLetter::F => 3,
+            NoteLetter::G => 4,
+            NoteLetter::A => 5,
+            NoteLetter::B => 6
+        }
+    }
+
+    /// Creates a letter from its position counted in steps from C
+    pub fn from_step(step: u32) -> NoteLetter {
+        match step % 7 {
+            0 => NoteLetter::C,
+            1 => NoteLetter::D,
+            2 => NoteLetter::E,
+            3 => NoteLetter::F,
+            4 => NoteLetter::G,
+            5 => NoteLetter::A,
+            _ => NoteLetter::B
+        }
+    }
+}
+
 impl TryFrom<&str> for NoteLetter {
-    type Error = &'static str;
+    type Error = Error;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
             "A" => Ok(Self::A),
@@ -18,7 +62,7 @@ impl TryFrom<&str> for NoteLetter {
             "E" => Ok(Self::E),
             "F" => Ok(Self::F),
             "G" => Ok(Self::G),
-            _ => Err("Input string does not correspond to a known NoteLetter")
+            _ => Err(Error::UnknownName { kind: "NoteLetter", name: value.to_string() })
         }
     }
 }
@@ -45,14 +89,55 @@ pub fn next_note_letter(note_letter: &NoteLetter) -> NoteLetter {
 pub enum NoteAccidental{
     Natural,
     Flat,
-    Sharp
+    Sharp,
+    DoubleFlat,
+    DoubleSharp
+}
+
+impl NoteAccidental {
+    /// Returns the number of semitones the accidental adds to a natural note
+    pub fn get_offset(&self) -> i32 {
+        match self {
+            NoteAccidental::DoubleFlat => -2,
+            NoteAccidental::Flat => -1,
+            NoteAccidental::Natural => 0,
+            NoteAccidental::Sharp => 1,
+            NoteAccidental::DoubleSharp => 2
+        }
+    }
+
+    /// Creates an accidental from a number of semitones added to a natural note
+    pub fn from_offset(offset: i32) -> Option<NoteAccidental> {
+        match offset {
+            -2 => Some(NoteAccidental::DoubleFlat),
+            -1 => Some(NoteAccidental::Flat),
+            0 => Some(NoteAccidental::Natural),
+            1 => Some(NoteAccidental::Sharp),
+            2 => Some(NoteAccidental::DoubleSharp),
+            _ => None
+        }
+    }
+}
+
+impl TryFrom<&str> for NoteAccidental {
+    type Error = Error;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "" => Ok(Self::Natural),
+            "b" => Ok(Self::Flat),
+            "#" => Ok(Self::Sharp),
+            "bb" => Ok(Self::DoubleFlat),
+            "##" => Ok(Self::DoubleSharp),
+            _ => Err(Error::UnknownName { kind: "NoteAccidental", name: value.to_string() })
+        }
+    }
 }
 
 pub fn all_note_accidentals() -> Vec<NoteAccidental> {
-    vec![ NoteAccidental::Natural, NoteAccidental::Flat, NoteAccidental::Sharp ]
+    vec![ NoteAccidental::Natural, NoteAccidental::Flat, NoteAccidental::Sharp, NoteAccidental::DoubleFlat, NoteAccidental::DoubleSharp ]
 }
 
-#[derive(Debug, Clone, Copy, Hash)]
+#[derive(Debug, Clone, Copy)]
 pub struct Note{
     letter: NoteLetter,
     accidental: NoteAccidental
@@ -63,16 +148,16 @@ impl Note{
 
     /// Creates a new music note
     pub fn new(letter: NoteLetter, accidental: NoteAccidental) -> Note {
-        Note{letter: letter, accidental: accidental}
+        Note{letter, accidental}
     }
 
     pub fn get_letter(&self) -> NoteLetter { self.letter }
     pub fn get_accidental(&self) -> NoteAccidental { self.accidental }
 
     /// Returns the note's numeral based on a root note e.g. Eb from C is 3b
-    pub fn as_numeral(&self, scale_root: &Note, index: usize) -> String {
-        let interval_name = Interval::from_notes(scale_root, &self).to_string();
-        match interval_name.as_str() {
+    pub fn as_numeral(&self, scale_root: &Note, index: usize) -> Result<String, Error> {
+        let interval = Interval::new( Interval::from_notes(scale_root, self).get_value() % 12 );
+        let numeral = match interval.to_string().as_str() {
             "P1" => String::from("R"),
             "m2" => String::from("2b"),
             "M2" => {
@@ -130,26 +215,20 @@ impl Note{
                 }
             },
             "M7" => String::from("7"),
-            _ => String::from("")
-        }
+            _ => return Err(Error::UnknownInterval(interval))
+        };
+        Ok(numeral)
     }
     
     /// Returns the note's numerical index
     pub fn get_index(&self) -> u32 {
-        let res : u32 = match self.letter {
-            NoteLetter::C => 0,
-            NoteLetter::D => 2,
-            NoteLetter::E => 4,
-            NoteLetter::F => 5,
-            NoteLetter::G => 7,
-            NoteLetter::A => 9,
-            NoteLetter::B => 11
-        };
-        match self.accidental {
-            NoteAccidental::Flat => if res == 0 { 11 } else { res - 1 },
-            NoteAccidental::Sharp => if res == 11 { 0 } else { res + 1 },
-            _ => res
-        }
+        self.get_offset_index().rem_euclid(12) as u32
+    }
+
+    /// Returns the index of the note's letter moved by its accidental, which may fall outside of
+    /// the octave e.g. -1 for Cb or 12 for B#
+    pub(crate) fn get_offset_index(&self) -> i32 {
+        self.letter.get_index() as i32 + self.accidental.get_offset()
     }
 
     /// Returns the note's enharmonic equivalents
@@ -158,9 +237,9 @@ impl Note{
         let all_other_letters : Vec<NoteLetter> = all_note_letters().into_iter()
             .filter(|&elt| elt != self.get_letter() )
             .collect();
-        for note_letter in all_other_letters.into_iter() {
-            for accidental in all_note_accidentals().iter() {
-                let note = Note::new( note_letter.clone(), accidental.clone() );
+        for note_letter in all_other_letters {
+            for accidental in all_note_accidentals() {
+                let note = Note::new( note_letter, accidental );
                 if note == *self { res.push(note); }
             }
             if res.len() == 2 { break; }
@@ -168,21 +247,28 @@ impl Note{
         res
     }
 
+    /// Returns the enharmonic equivalent written with the given letter e.g. E respelled with F is Fb
+    pub fn respell(&self, letter: NoteLetter) -> Result<Note, Error> {
+        let offset = (self.get_index() as i32 - letter.get_index() as i32 + 6).rem_euclid(12) - 6;
+        match NoteAccidental::from_offset(offset) {
+            Some(accidental) => Ok( Note::new(letter, accidental) ),
+            None => Err(Error::UnspellableNote { letter, offset })
+        }
+    }
+
     /// Returns the note one semitone above
     pub fn next(&self) -> Note {
         let target_idx = if self.get_index() == 11 { 0 } else { self.get_index() + 1 };
         let candidates : Vec<Note> = all_note_letters().into_iter()
-            .cartesian_product( all_note_accidentals().into_iter() )
-            .map(|elt| Note::new(elt.0.clone(), elt.1.clone()) )
+            .cartesian_product( all_note_accidentals() )
+            .map(|elt| Note::new(elt.0, elt.1) )
             .filter(|&note| note.get_index() == target_idx )
             .collect();
-        match candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Natural) {
-            Some(note) => { return *note; }
-            _ => {}
+        if let Some(note) = candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Natural) {
+            return *note;
         }
-        match candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Sharp) {
-            Some(note) => { return *note; }
-            _ => {}
+        if let Some(note) = candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Sharp) {
+            return *note;
         }
         candidates[0]
     }
@@ -191,20 +277,28 @@ impl Note{
     pub fn previous(&self) -> Note {
         let target_idx = if self.get_index() == 0 { 11 } else { self.get_index() - 1 };
         let candidates : Vec<Note> = all_note_letters().into_iter()
-            .cartesian_product( all_note_accidentals().into_iter() )
-            .map(|elt| Note::new(elt.0.clone(), elt.1.clone()) )
+            .cartesian_product( all_note_accidentals() )
+            .map(|elt| Note::new(elt.0, elt.1) )
             .filter(|&note| note.get_index() == target_idx )
             .collect();
-        match candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Natural) {
-            Some(note) => { return *note; }
-            _ => {}
+        if let Some(note) = candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Natural) {
+            return *note;
         }
-        match candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Flat) {
-            Some(note) => { return *note; }
-            _ => {}
+        if let Some(note) = candidates.iter().find(|&&note| note.get_accidental() == NoteAccidental::Flat) {
+            return *note;
         }
         candidates[0]
     }
+
+    /// Returns the note one semitone above spelled following a policy
+    pub fn next_with(&self, policy: &SpellingPolicy) -> Note {
+        policy.spell( &self.next() )
+    }
+
+    /// Returns the note one semitone below spelled following a policy
+    pub fn previous_with(&self, policy: &SpellingPolicy) -> Note {
+        policy.spell( &self.previous() )
+    }
 }
 
 impl fmt::Display for Note {
@@ -221,34 +315,92 @@ impl fmt::Display for Note {
         match self.accidental {
             NoteAccidental::Flat => write!(f, "{}b", res),
             NoteAccidental::Sharp => write!(f, "{}#", res),
+            NoteAccidental::DoubleFlat => write!(f, "{}bb", res),
+            NoteAccidental::DoubleSharp => write!(f, "{}##", res),
             _ => write!(f, "{}", res)
         }
     }
 }
 
+/// Error returned when a note name cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNoteError {
+    Empty,
+    InvalidLetter(char),
+    InvalidAccidental(char),
+    UnsupportedAccidental(i32),
+    InvalidOctave(String),
+    OutOfRange(String)
+}
+
+impl fmt::Display for ParseNoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Note name is empty"),
+            Self::InvalidLetter(c) => write!(f, "'{}' is not a note letter", c),
+            Self::InvalidAccidental(c) => write!(f, "'{}' is not an accidental", c),
+            Self::UnsupportedAccidental(offset) => write!(f, "Accidentals shifting a note by {} semitones are not supported", offset),
+            Self::InvalidOctave(s) => write!(f, "'{}' is not a valid octave", s),
+            Self::OutOfRange(s) => write!(f, "'{}' is below C-1", s)
+        }
+    }
+}
+
+impl std::error::Error for ParseNoteError {}
+
+/// Parses a note name at the start of a string and returns it with the unparsed remainder
+pub(crate) fn parse_note_prefix(value: &str) -> Result<(Note, &str), ParseNoteError> {
+    let value = value.trim();
+    let mut chars = value.char_indices();
+    let letter = match chars.next() {
+        Some((_, c)) => match c.to_ascii_uppercase() {
+            'A' => NoteLetter::A,
+            'B' => NoteLetter::B,
+            'C' => NoteLetter::C,
+            'D' => NoteLetter::D,
+            'E' => NoteLetter::E,
+            'F' => NoteLetter::F,
+            'G' => NoteLetter::G,
+            _ => return Err(ParseNoteError::InvalidLetter(c))
+        },
+        None => return Err(ParseNoteError::Empty)
+    };
+    let mut offset = 0;
+    let mut remainder = "";
+    for (idx, c) in chars {
+        match c {
+            '#' | '♯' => offset += 1,
+            'b' | '♭' => offset -= 1,
+            'x' | '𝄪' => offset += 2,
+            '𝄫' => offset -= 2,
+            '♮' => {},
+            _ => {
+                remainder = &value[idx..];
+                break;
+            }
+        }
+    }
+    match NoteAccidental::from_offset(offset) {
+        Some(accidental) => Ok( (Note::new(letter, accidental), remainder) ),
+        None => Err(ParseNoteError::UnsupportedAccidental(offset))
+    }
+}
+
+impl FromStr for Note {
+    type Err = ParseNoteError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (note, remainder) = parse_note_prefix(s)?;
+        match remainder.chars().next() {
+            Some(c) => Err(ParseNoteError::InvalidAccidental(c)),
+            None => Ok(note)
+        }
+    }
+}
+
 impl TryFrom<&str> for Note {
-    type Error = &'static str;
+    type Error = Error;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "C" => Ok( Note::new(NoteLetter::C, NoteAccidental::Natural) ),
-            "C#" => Ok( Note::new(NoteLetter::C, NoteAccidental::Sharp) ),
-            "Db" => Ok( Note::new(NoteLetter::D, NoteAccidental::Flat) ),
-            "D" => Ok( Note::new(NoteLetter::D, NoteAccidental::Natural) ),
-            "D#" => Ok( Note::new(NoteLetter::D, NoteAccidental::Sharp) ),
-            "Eb" => Ok( Note::new(NoteLetter::E, NoteAccidental::Flat) ),
-            "E" => Ok( Note::new(NoteLetter::E, NoteAccidental::Natural) ),
-            "F" => Ok( Note::new(NoteLetter::F, NoteAccidental::Natural) ),
-            "F#" => Ok( Note::new(NoteLetter::F, NoteAccidental::Sharp) ),
-            "Gb" => Ok( Note::new(NoteLetter::G, NoteAccidental::Flat) ),
-            "G" => Ok( Note::new(NoteLetter::G, NoteAccidental::Natural) ),
-            "G#" => Ok( Note::new(NoteLetter::G, NoteAccidental::Sharp) ),
-            "Ab" => Ok( Note::new(NoteLetter::A, NoteAccidental::Flat) ),
-            "A" => Ok( Note::new(NoteLetter::A, NoteAccidental::Natural) ),
-            "A#" => Ok( Note::new(NoteLetter::A, NoteAccidental::Sharp) ),
-            "Bb" => Ok( Note::new(NoteLetter::B, NoteAccidental::Flat) ),
-            "B" => Ok( Note::new(NoteLetter::B, NoteAccidental::Natural) ),
-            _ => Err("Failed to generate Note from string")
-        }
+        value.parse().map_err(Error::ParseNote)
     }
 }
 
@@ -270,4 +422,24 @@ impl PartialEq for Note {
     }
 }
 
-impl Eq for Note {}
\ No newline at end of file
+impl Eq for Note {}
+
+impl Hash for Note {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get_index().hash(state);
+    }
+}
+
+impl Add<Interval> for Note {
+    type Output = Note;
+    fn add(self, interval: Interval) -> Note {
+        interval.apply(&self)
+    }
+}
+
+impl Sub<Interval> for Note {
+    type Output = Note;
+    fn sub(self, interval: Interval) -> Note {
+        (-interval).apply(&self)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,214 @@
+use std::fmt;
+use crate::{Note, MIDINote, Interval, IntervalQuality, Chord, ChordPosition, ChordModifier};
+use crate::chord::all_chord_shapes;
+
+/// Score given to each chord tone found in the notes
+const MATCHED_TONE_SCORE : f64 = 1.0;
+
+/// Penalty for each note which is not a chord tone
+const EXTRA_NOTE_PENALTY : f64 = 1.0;
+
+/// Penalty for a bass note outside of the chord, the chord being read as a slash chord
+const SLASH_BASS_PENALTY : f64 = 0.5;
+
+/// Bonus given when the root is in the bass
+const ROOT_BASS_BONUS : f64 = 0.5;
+
+/// Penalty for a chord tone missing from the notes. The fifth and the eleventh of thirteenth
+/// chords are commonly left out of voicings while the third and the seventh define the chord
+fn missing_tone_penalty(interval: &Interval, tones_count: usize) -> f64 {
+    match (interval.get_number(), interval.get_quality()) {
+        (1, _) => 2.0,
+        (5, IntervalQuality::Perfect) => 0.25,
+        (11, _) if tones_count > 6 => 0.25,
+        (3, _) | (7, _) => 2.5,
+        _ => 1.5
+    }
+}
+
+/// A chord recognised from notes along with how well it fits them
+#[derive(Debug, Clone)]
+pub struct ChordMatch {
+    chord: Chord,
+    score: f64,
+    missing_tones: Vec<Interval>,
+    extra_notes: Vec<Note>
+}
+
+impl ChordMatch {
+
+    /// Returns the chord, missing tones being marked as omitted e.g. C7(no5)
+    pub fn get_chord(&self) -> Chord { self.chord.clone() }
+    pub fn get_score(&self) -> f64 { self.score }
+
+    /// Returns the intervals from the root of the chord tones missing from the notes
+    pub fn get_missing_tones(&self) -> Vec<Interval> { self.missing_tones.clone() }
+
+    /// Returns the notes which are not chord tones, a slash chord's bass note excepted
+    pub fn get_extra_notes(&self) -> Vec<Note> { self.extra_notes.clone() }
+}
+
+impl fmt::Display for ChordMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:.2})", self.chord, self.score)
+    }
+}
+
+/// Recognises the chords the notes may be read as, best matches first. The first note is taken
+/// as the bass and the order of the others does not matter. Candidates are scored on the chord
+/// tones found, the chord tones missing, the notes outside of the chord and the bass note. Readings
+/// of the same notes from the same root are only given once
+pub fn recognise_chords(notes: &[Note]) -> Vec<ChordMatch> {
+    let mut pitch_set : Vec<Note> = Vec::new();
+    for note in notes.iter() {
+        if !pitch_set.contains(note) {
+            pitch_set.push( *note );
+        }
+    }
+    let bass_note = match pitch_set.first() {
+        Some(note) => *note,
+        None => return Vec::new()
+    };
+    let shapes = all_chord_shapes();
+
+    // Any note may be any tone of any chord, so roots are found by going back from each note.
+    // A root found among the notes is spelled as given
+    let mut candidates : Vec<(Note, usize)> = Vec::new();
+    for (shape_idx, (quality, modifiers)) in shapes.iter().enumerate() {
+        let tones = Chord::with_modifiers( bass_note, *quality, modifiers, ChordPosition::Root ).get_tones();
+        for note in pitch_set.iter() {
+            for tone in tones.iter() {
+                let root_note = *note - *tone;
+                let root_note = pitch_set.iter().find(|elt| **elt == root_note).cloned().unwrap_or(root_note);
+                if !candidates.iter().any(|elt| elt.0 == root_note && elt.1 == shape_idx) {
+                    candidates.push( (root_note, shape_idx) );
+                }
+            }
+        }
+    }
+
+    let mut res : Vec<ChordMatch> = Vec::new();
+    for (root_note, shape_idx) in candidates.into_iter() {
+        let (quality, modifiers) = &shapes[shape_idx];
+        let chord = Chord::with_modifiers( root_note, *quality, modifiers, ChordPosition::Root );
+        let tones = chord.get_tones();
+        let chord_notes = chord.get_notes();
+        let matched_count = chord_notes.iter().filter(|note| pitch_set.contains(note)).count();
+        if matched_count < pitch_set.len().min(3) || 2 * matched_count < tones.len() {
+            continue;
+        }
+        let missing_tones : Vec<Interval> = tones.iter().zip(chord_notes.iter())
+            .filter(|elt| !pitch_set.contains(elt.1))
+            .map(|elt| *elt.0)
+            .collect();
+        // Added and altered tones name the chord so they cannot be missing
+        let modified_missing = modifiers.iter().any(|modifier| match modifier {
+            ChordModifier::Add(interval) | ChordModifier::Alter(interval) | ChordModifier::Suspend(interval) => missing_tones.contains(interval),
+            ChordModifier::Omit(_) => false
+        });
+        if modified_missing {
+            continue;
+        }
+        let slash_bass = !chord_notes.contains(&bass_note);
+        let extra_notes : Vec<Note> = pitch_set.iter()
+            .filter(|note| !chord_notes.contains(note) && **note != bass_note)
+            .cloned()
+            .collect();
+
+        let mut score = MATCHED_TONE_SCORE * matched_count as f64
+            - EXTRA_NOTE_PENALTY * extra_notes.len() as f64;
+        for tone in missing_tones.iter() {
+            score -= missing_tone_penalty(tone, tones.len());
+        }
+        if slash_bass {
+            score -= SLASH_BASS_PENALTY;
+        } else if bass_note == root_note {
+            score += ROOT_BASS_BONUS;
+        }
+        if score <= 0.0 {
+            continue;
+        }
+
+        let mut chord_modifiers = modifiers.clone();
+        chord_modifiers.extend( missing_tones.iter().map(|tone| ChordModifier::Omit(tone.get_number())) );
+        let played_chord = Chord::with_modifiers( root_note, *quality, &chord_modifiers, ChordPosition::Root );
+        // Omitting a tone must not remove another tone of the same number e.g. the #5 of 7alt
+        if played_chord.get_notes().len() != matched_count {
+            continue;
+        }
+        res.push( ChordMatch {
+            chord: played_chord.with_bass(&bass_note),
+            score,
+            missing_tones,
+            extra_notes
+        });
+    }
+    res.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    // Other readings of the same notes from the same root only restate the best one e.g. C7(b5)(no5) for C7(no5)
+    let mut kept : Vec<ChordMatch> = Vec::new();
+    for chord_match in res.into_iter() {
+        let chord_notes = chord_match.chord.get_notes();
+        let restated = kept.iter().any(|elt| {
+            let notes = elt.chord.get_notes();
+            elt.chord.get_root() == chord_match.chord.get_root()
+                && notes.len() == chord_notes.len()
+                && notes.iter().all(|note| chord_notes.contains(note))
+        });
+        if !restated {
+            kept.push( chord_match );
+        }
+    }
+    kept
+}
+
+/// A chord recognised from sounding notes along with the way the notes are spread
+#[derive(Debug, Clone)]
+pub struct VoicingMatch {
+    chord_match: ChordMatch,
+    notes: Vec<MIDINote>
+}
+
+impl VoicingMatch {
+
+    pub fn get_chord_match(&self) -> ChordMatch { self.chord_match.clone() }
+    pub fn get_chord(&self) -> Chord { self.chord_match.get_chord() }
+
+    /// Returns the notes of the voicing from the lowest
+    pub fn get_notes(&self) -> Vec<MIDINote> { self.notes.clone() }
+
+    /// Returns the intervals between consecutive notes of the voicing from the lowest
+    pub fn get_spacing(&self) -> Vec<Interval> {
+        self.notes.windows(2)
+            .map(|w| Interval::from_midi_notes(&w[0], &w[1]) )
+            .collect()
+    }
+
+    /// Returns the interval from the lowest to the highest note
+    pub fn get_span(&self) -> Interval {
+        Interval::from_midi_notes(&self.notes[0], &self.notes[self.notes.len() - 1])
+    }
+
+    /// Checks if the notes above the bass fit within an octave
+    pub fn is_close_position(&self) -> bool {
+        let upper_notes = if self.notes.len() > 1 { &self.notes[1..] } else { &self.notes[..] };
+        Interval::from_midi_notes(&upper_notes[0], &upper_notes[upper_notes.len() - 1]).get_value() < 12
+    }
+}
+
+impl fmt::Display for VoicingMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let spacing : Vec<String> = self.get_spacing().iter().map(|interval| interval.to_string()).collect();
+        write!(f, "{} [{}]", self.chord_match, spacing.join(","))
+    }
+}
+
+/// Recognises the chords sounding notes may be read as, best matches first, whatever their
+/// order or octaves. The lowest note is the bass
+pub fn recognise_voicing(notes: &[MIDINote]) -> Vec<VoicingMatch> {
+    let mut sorted_notes = notes.to_vec();
+    sorted_notes.sort();
+    let pitch_classes : Vec<Note> = sorted_notes.iter().map(|note| note.get_note()).collect();
+    recognise_chords(&pitch_classes).into_iter()
+        .map(|chord_match| VoicingMatch { chord_match, notes: sorted_notes.clone() })
+        .collect()
+}
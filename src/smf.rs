@@ -0,0 +1,663 @@
+use std::fmt;
+use crate::{MIDINote, Key, KeyQuality, Error, key_from_signature};
+
+/// Tempo of a MIDI file with no tempo event, 120 beats per minute
+const DEFAULT_TEMPO : u32 = 500_000;
+
+/// Longest delta time between two events, the most a variable length quantity of 4 bytes holds
+const MAX_DELTA : u64 = 0x0FFF_FFFF;
+
+/// A note played in a MIDI file, times being counted in ticks from the start of the track
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NoteEvent {
+    tick: u64,
+    duration: u64,
+    note: MIDINote,
+    velocity: u8,
+    channel: u8
+}
+
+impl NoteEvent {
+
+    /// Creates a note starting at a tick and lasting a number of ticks, channels counting from 0
+    pub fn new(tick: u64, duration: u64, note: MIDINote, velocity: u8, channel: u8) -> NoteEvent {
+        NoteEvent{ tick, duration, note, velocity, channel }
+    }
+
+    pub fn get_tick(&self) -> u64 { self.tick }
+    pub fn get_duration(&self) -> u64 { self.duration }
+    pub fn get_note(&self) -> MIDINote { self.note }
+    pub fn get_velocity(&self) -> u8 { self.velocity }
+    pub fn get_channel(&self) -> u8 { self.channel }
+}
+
+/// A change of instrument on a channel
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ProgramChange {
+    tick: u64,
+    channel: u8,
+    program: u8
+}
+
+impl ProgramChange {
+
+    pub fn new(tick: u64, channel: u8, program: u8) -> ProgramChange {
+        ProgramChange{ tick, channel, program }
+    }
+
+    pub fn get_tick(&self) -> u64 { self.tick }
+    pub fn get_channel(&self) -> u8 { self.channel }
+    pub fn get_program(&self) -> u8 { self.program }
+}
+
+/// A change of tempo, given in microseconds per quarter note
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Tempo {
+    tick: u64,
+    microseconds_per_quarter: u32
+}
+
+impl Tempo {
+
+    pub fn new(tick: u64, microseconds_per_quarter: u32) -> Tempo {
+        Tempo{ tick, microseconds_per_quarter }
+    }
+
+    /// Creates a tempo change from a number of quarter notes per minute
+    pub fn from_bpm(tick: u64, bpm: f64) -> Tempo {
+        Tempo::new( tick, (60_000_000.0 / bpm).round() as u32 )
+    }
+
+    pub fn get_tick(&self) -> u64 { self.tick }
+    pub fn get_microseconds_per_quarter(&self) -> u32 { self.microseconds_per_quarter }
+
+    /// Returns the number of quarter notes per minute
+    pub fn get_bpm(&self) -> f64 {
+        60_000_000.0 / self.microseconds_per_quarter as f64
+    }
+}
+
+/// A change of time signature e.g. 6/8
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimeSignature {
+    tick: u64,
+    numerator: u8,
+    denominator: u32,
+    clocks_per_click: u8,
+    thirty_seconds_per_quarter: u8
+}
+
+impl TimeSignature {
+
+    /// Creates a time signature with a metronome click on every beat, the denominator being a power of 2
+    pub fn new(tick: u64, numerator: u8, denominator: u32) -> TimeSignature {
+        let clocks_per_click = (96 / denominator.max(1)).clamp(1, 255) as u8;
+        TimeSignature{ tick, numerator, denominator, clocks_per_click, thirty_seconds_per_quarter: 8 }
+    }
+
+    pub fn get_tick(&self) -> u64 { self.tick }
+    pub fn get_numerator(&self) -> u8 { self.numerator }
+    pub fn get_denominator(&self) -> u32 { self.denominator }
+
+    /// Returns the number of MIDI clocks between metronome clicks, there being 24 clocks in a quarter note
+    pub fn get_clocks_per_click(&self) -> u8 { self.clocks_per_click }
+
+    /// Returns the number of notated 32nd notes in a quarter note, usually 8
+    pub fn get_thirty_seconds_per_quarter(&self) -> u8 { self.thirty_seconds_per_quarter }
+}
+
+impl fmt::Display for TimeSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+/// A change of key signature
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct KeySignature {
+    tick: u64,
+    key: Key
+}
+
+impl KeySignature {
+
+    pub fn new(tick: u64, key: Key) -> KeySignature {
+        KeySignature{ tick, key }
+    }
+
+    pub fn get_tick(&self) -> u64 { self.tick }
+    pub fn get_key(&self) -> Key { self.key }
+}
+
+/// A meta event with no dedicated type e.g. a lyric or a marker, kept as its type and raw data
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaEvent {
+    tick: u64,
+    kind: u8,
+    data: Vec<u8>
+}
+
+impl MetaEvent {
+
+    pub fn new(tick: u64, kind: u8, data: Vec<u8>) -> MetaEvent {
+        MetaEvent{ tick, kind, data }
+    }
+
+    pub fn get_tick(&self) -> u64 { self.tick }
+    pub fn get_kind(&self) -> u8 { self.kind }
+    pub fn get_data(&self) -> Vec<u8> { self.data.clone() }
+
+    /// Returns the data read as text for text meta events, from 0x01 to 0x0F
+    pub fn get_text(&self) -> Option<String> {
+        if (0x01..=0x0F).contains(&self.kind) {
+            Some( String::from_utf8_lossy(&self.data).into_owned() )
+        } else {
+            None
+        }
+    }
+}
+
+/// A track of a MIDI file with its notes and the other events it holds
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Track {
+    name: Option<String>,
+    notes: Vec<NoteEvent>,
+    program_changes: Vec<ProgramChange>,
+    tempos: Vec<Tempo>,
+    time_signatures: Vec<TimeSignature>,
+    key_signatures: Vec<KeySignature>,
+    meta_events: Vec<MetaEvent>,
+    end_tick: u64
+}
+
+impl Track {
+
+    /// Creates a track from its notes, the track ending when the last note stops
+    pub fn new(name: Option<&str>, notes: Vec<NoteEvent>) -> Track {
+        let mut res = Track{ name: name.map(String::from), notes, ..Track::default() };
+        res.notes.sort_by_key(|event| (event.tick, event.note.get_index()));
+        res.end_tick = res.last_tick();
+        res
+    }
+
+    /// Returns the track with program changes added
+    pub fn with_program_changes(&self, program_changes: &[ProgramChange]) -> Track {
+        let mut res = self.clone();
+        res.program_changes.extend_from_slice(program_changes);
+        res.program_changes.sort_by_key(|event| event.tick);
+        res.end_tick = res.last_tick();
+        res
+    }
+
+    /// Returns the track with tempo changes added
+    pub fn with_tempos(&self, tempos: &[Tempo]) -> Track {
+        let mut res = self.clone();
+        res.tempos.extend_from_slice(tempos);
+        res.tempos.sort_by_key(|event| event.tick);
+        res.end_tick = res.last_tick();
+        res
+    }
+
+    /// Returns the track with time signature changes added
+    pub fn with_time_signatures(&self, time_signatures: &[TimeSignature]) -> Track {
+        let mut res = self.clone();
+        res.time_signatures.extend_from_slice(time_signatures);
+        res.time_signatures.sort_by_key(|event| event.tick);
+        res.end_tick = res.last_tick();
+        res
+    }
+
+    /// Returns the track with key signature changes added
+    pub fn with_key_signatures(&self, key_signatures: &[KeySignature]) -> Track {
+        let mut res = self.clone();
+        res.key_signatures.extend_from_slice(key_signatures);
+        res.key_signatures.sort_by_key(|event| event.tick);
+        res.end_tick = res.last_tick();
+        res
+    }
+
+    /// Returns the track with other meta events added
+    pub fn with_meta_events(&self, meta_events: &[MetaEvent]) -> Track {
+        let mut res = self.clone();
+        res.meta_events.extend_from_slice(meta_events);
+        res.meta_events.sort_by_key(|event| event.tick);
+        res.end_tick = res.last_tick();
+        res
+    }
+
+    /// Returns the track ending at a tick, or when its last event happens if that is later
+    pub fn with_end_tick(&self, end_tick: u64) -> Track {
+        let mut res = self.clone();
+        res.end_tick = end_tick.max(res.last_tick());
+        res
+    }
+
+    pub fn get_name(&self) -> Option<String> { self.name.clone() }
+
+    /// Returns the notes ordered by start time, then from the lowest
+    pub fn get_notes(&self) -> Vec<NoteEvent> { self.notes.clone() }
+    pub fn get_program_changes(&self) -> Vec<ProgramChange> { self.program_changes.clone() }
+    pub fn get_tempos(&self) -> Vec<Tempo> { self.tempos.clone() }
+    pub fn get_time_signatures(&self) -> Vec<TimeSignature> { self.time_signatures.clone() }
+    pub fn get_key_signatures(&self) -> Vec<KeySignature> { self.key_signatures.clone() }
+    pub fn get_meta_events(&self) -> Vec<MetaEvent> { self.meta_events.clone() }
+
+    /// Returns the tick at which the track ends
+    pub fn get_end_tick(&self) -> u64 { self.end_tick }
+
+    /// Returns the tick of the last event, notes ending when they stop
+    fn last_tick(&self) -> u64 {
+        self.notes.iter().map(|event| event.tick + event.duration)
+            .chain(self.program_changes.iter().map(|event| event.tick))
+            .chain(self.tempos.iter().map(|event| event.tick))
+            .chain(self.time_signatures.iter().map(|event| event.tick))
+            .chain(self.key_signatures.iter().map(|event| event.tick))
+            .chain(self.meta_events.iter().map(|event| event.tick))
+            .max()
+            .unwrap_or(0)
+            .max(self.end_tick)
+    }
+}
+
+/// A Standard MIDI File of format 0, a single track, or format 1, tracks played together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MidiFile {
+    format: u16,
+    ticks_per_quarter: u16,
+    tracks: Vec<Track>
+}
+
+impl MidiFile {
+
+    /// Creates a MIDI file of format 0, holding a single track, or of format 1, holding tracks
+    /// played together with the tempo map usually in the first one
+    pub fn new(format: u16, ticks_per_quarter: u16, tracks: Vec<Track>) -> Result<MidiFile, Error> {
+        if format > 1 {
+            return Err(Error::InvalidMidiFile(format!("format {} is not supported", format)));
+        }
+        if format == 0 && tracks.len() != 1 {
+            return Err(Error::InvalidMidiFile(format!("format 0 file with {} tracks", tracks.len())));
+        }
+        if ticks_per_quarter == 0 || ticks_per_quarter & 0x8000 != 0 {
+            return Err(Error::InvalidMidiFile(format!("{} ticks per quarter note is out of range", ticks_per_quarter)));
+        }
+        Ok( MidiFile{ format, ticks_per_quarter, tracks } )
+    }
+
+    pub fn get_format(&self) -> u16 { self.format }
+    pub fn get_ticks_per_quarter(&self) -> u16 { self.ticks_per_quarter }
+    pub fn get_tracks(&self) -> Vec<Track> { self.tracks.clone() }
+
+    /// Returns the notes of every track ordered by start time
+    pub fn get_notes(&self) -> Vec<NoteEvent> {
+        let mut res : Vec<NoteEvent> = self.tracks.iter().flat_map(|track| track.notes.iter().cloned()).collect();
+        res.sort_by_key(|event| (event.tick, event.note.get_index()));
+        res
+    }
+
+    /// Returns the tempo changes of every track ordered by time
+    pub fn get_tempo_map(&self) -> Vec<Tempo> {
+        let mut res : Vec<Tempo> = self.tracks.iter().flat_map(|track| track.tempos.iter().cloned()).collect();
+        res.sort_by_key(|tempo| tempo.tick);
+        res
+    }
+
+    /// Returns the time signature changes of every track ordered by time
+    pub fn get_time_signatures(&self) -> Vec<TimeSignature> {
+        let mut res : Vec<TimeSignature> = self.tracks.iter().flat_map(|track| track.time_signatures.iter().cloned()).collect();
+        res.sort_by_key(|time_signature| time_signature.tick);
+        res
+    }
+
+    /// Returns the key signature changes of every track ordered by time
+    pub fn get_key_signatures(&self) -> Vec<KeySignature> {
+        let mut res : Vec<KeySignature> = self.tracks.iter().flat_map(|track| track.key_signatures.iter().cloned()).collect();
+        res.sort_by_key(|key_signature| key_signature.tick);
+        res
+    }
+
+    /// Returns the time in seconds of a tick following the tempo map, the tempo being 120 beats
+    /// per minute until the first tempo change
+    pub fn tick_to_seconds(&self, tick: u64) -> f64 {
+        let mut microseconds = 0.0;
+        let mut last_tick = 0;
+        let mut tempo = DEFAULT_TEMPO;
+        for change in self.get_tempo_map().iter().take_while(|change| change.tick < tick) {
+            microseconds += (change.tick - last_tick) as f64 * tempo as f64;
+            last_tick = change.tick;
+            tempo = change.microseconds_per_quarter;
+        }
+        microseconds += (tick - last_tick) as f64 * tempo as f64;
+        microseconds / self.ticks_per_quarter as f64 / 1_000_000.0
+    }
+}
+
+/// Reads the big-endian numbers and variable length quantities of a MIDI file
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Reader<'a> {
+
+    fn new(bytes: &'a [u8]) -> Reader<'a> {
+        Reader{ bytes, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool { self.pos >= self.bytes.len() }
+
+    fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() - self.pos < count {
+            return Err(Error::InvalidMidiFile(String::from("unexpected end of data")));
+        }
+        let res = &self.bytes[self.pos..self.pos + count];
+        self.pos += count;
+        Ok( res )
+    }
+
+    fn peek_u8(&self) -> Result<u8, Error> {
+        self.bytes.get(self.pos).cloned()
+            .ok_or_else(|| Error::InvalidMidiFile(String::from("unexpected end of data")))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok( self.read_bytes(1)?[0] )
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.read_bytes(2)?;
+        Ok( u16::from_be_bytes([bytes[0], bytes[1]]) )
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.read_bytes(4)?;
+        Ok( u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) )
+    }
+
+    /// Reads a variable length quantity, 7 bits per byte with the top bit set on all but the last one
+    fn read_varlen(&mut self) -> Result<u32, Error> {
+        let mut res : u32 = 0;
+        for _ in 0..4 {
+            let byte = self.read_u8()?;
+            res = (res << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Ok( res );
+            }
+        }
+        Err(Error::InvalidMidiFile(String::from("variable length quantity longer than 4 bytes")))
+    }
+}
+
+/// Ends the first sounding note of a key on a channel, adding it to the track
+fn note_off(track: &mut Track, sounding: &mut Vec<(u8, u8, u64, u8)>, tick: u64, channel: u8, key: u8) -> Result<(), Error> {
+    if let Some(idx) = sounding.iter().position(|elt| elt.0 == channel && elt.1 == key) {
+        let (_, _, start, velocity) = sounding.remove(idx);
+        let note = MIDINote::from_midi_number(key as u32)?;
+        track.notes.push( NoteEvent::new(start, tick - start, note, velocity, channel) );
+    }
+    Ok( () )
+}
+
+/// Reads the events of a track chunk. Notes are paired with the first note off of the same key
+/// and channel, a note on with a velocity of 0 being a note off, and notes still sounding at
+/// the end of the track last until then
+fn read_track(data: &[u8]) -> Result<Track, Error> {
+    let mut reader = Reader::new(data);
+    let mut track = Track::default();
+    let mut tick : u64 = 0;
+    let mut running_status : Option<u8> = None;
+    // Notes waiting for their note off: channel, key, start tick and velocity
+    let mut sounding : Vec<(u8, u8, u64, u8)> = Vec::new();
+
+    while !reader.is_empty() {
+        tick += reader.read_varlen()? as u64;
+        let status = if reader.peek_u8()? < 0x80 {
+            running_status.ok_or_else(|| Error::InvalidMidiFile(String::from("data byte found without a running status")))?
+        } else {
+            reader.read_u8()?
+        };
+        if status < 0xF0 {
+            running_status = Some( status );
+        }
+        let channel = status & 0x0F;
+        match status & 0xF0 {
+            0x80 => {
+                let key = reader.read_u8()?;
+                reader.read_u8()?;
+                note_off(&mut track, &mut sounding, tick, channel, key)?;
+            },
+            0x90 => {
+                let key = reader.read_u8()?;
+                let velocity = reader.read_u8()?;
+                if velocity == 0 {
+                    note_off(&mut track, &mut sounding, tick, channel, key)?;
+                } else {
+                    sounding.push( (channel, key, tick, velocity) );
+                }
+            },
+            0xA0 | 0xB0 | 0xE0 => { reader.read_bytes(2)?; },
+            0xC0 => {
+                let program = reader.read_u8()?;
+                track.program_changes.push( ProgramChange::new(tick, channel, program) );
+            },
+            0xD0 => { reader.read_u8()?; },
+            _ => match status {
+                0xF0 | 0xF7 => {
+                    let length = reader.read_varlen()? as usize;
+                    reader.read_bytes(length)?;
+                },
+                0xFF => {
+                    let kind = reader.read_u8()?;
+                    let length = reader.read_varlen()? as usize;
+                    let data = reader.read_bytes(length)?;
+                    match (kind, data.len()) {
+                        (0x2F, _) => break,
+                        (0x03, _) => track.name = Some( String::from_utf8_lossy(data).into_owned() ),
+                        (0x51, 3) => track.tempos.push( Tempo::new(tick, u32::from_be_bytes([0, data[0], data[1], data[2]])) ),
+                        (0x58, 4) => track.time_signatures.push( TimeSignature {
+                            tick,
+                            numerator: data[0],
+                            denominator: 1u32.checked_shl(data[1] as u32)
+                                .ok_or_else(|| Error::InvalidMidiFile(format!("time signature denominator 2^{} is too large", data[1])))?,
+                            clocks_per_click: data[2],
+                            thirty_seconds_per_quarter: data[3]
+                        }),
+                        (0x59, 2) => {
+                            let signature = data[0] as i8 as i32;
+                            if !(-7..=7).contains(&signature) {
+                                return Err(Error::InvalidMidiFile(format!("key signature of {} is out of range", signature)));
+                            }
+                            let quality = if data[1] == 0 { KeyQuality::Major } else { KeyQuality::Minor };
+                            track.key_signatures.push( KeySignature::new(tick, key_from_signature(signature, quality)?) );
+                        },
+                        _ => track.meta_events.push( MetaEvent::new(tick, kind, data.to_vec()) )
+                    }
+                },
+                _ => return Err(Error::InvalidMidiFile(format!("unexpected status byte {:#04X}", status)))
+            }
+        }
+    }
+    while let Some((channel, key, _, _)) = sounding.first().cloned() {
+        note_off(&mut track, &mut sounding, tick, channel, key)?;
+    }
+    track.end_tick = tick;
+    track.notes.sort_by_key(|event| (event.tick, event.note.get_index()));
+    Ok( track )
+}
+
+/// Reads a Standard MIDI File of format 0 or 1 with a division in ticks per quarter note.
+/// Chunks other than the header and tracks are skipped
+pub fn read_smf(bytes: &[u8]) -> Result<MidiFile, Error> {
+    let mut reader = Reader::new(bytes);
+    if reader.read_bytes(4).ok() != Some( b"MThd".as_ref() ) {
+        return Err(Error::InvalidMidiFile(String::from("missing MThd header")));
+    }
+    let header_length = reader.read_u32()? as usize;
+    let header = reader.read_bytes(header_length)?;
+    let mut header_reader = Reader::new(header);
+    let format = header_reader.read_u16()?;
+    let track_count = header_reader.read_u16()? as usize;
+    let ticks_per_quarter = header_reader.read_u16()?;
+    if format > 1 {
+        return Err(Error::InvalidMidiFile(format!("format {} is not supported", format)));
+    }
+    if ticks_per_quarter & 0x8000 != 0 {
+        return Err(Error::InvalidMidiFile(String::from("SMPTE time division is not supported")));
+    }
+
+    let mut tracks = Vec::new();
+    while !reader.is_empty() && tracks.len() < track_count {
+        let kind = reader.read_bytes(4)?;
+        let length = reader.read_u32()? as usize;
+        let data = reader.read_bytes(length)?;
+        if kind == b"MTrk" {
+            tracks.push( read_track(data)? );
+        }
+    }
+    if tracks.len() != track_count {
+        return Err(Error::InvalidMidiFile(format!("expected {} tracks but found {}", track_count, tracks.len())));
+    }
+    MidiFile::new(format, ticks_per_quarter, tracks)
+}
+
+/// Appends a variable length quantity, 7 bits per byte with the top bit set on all but the last one
+fn write_varlen(bytes: &mut Vec<u8>, value: u32) {
+    let mut groups = vec![ (value & 0x7F) as u8 ];
+    let mut rest = value >> 7;
+    while rest > 0 {
+        groups.push( (rest & 0x7F) as u8 | 0x80 );
+        rest >>= 7;
+    }
+    bytes.extend( groups.into_iter().rev() );
+}
+
+/// Appends the delta time before an event, failing if it does not fit in 4 bytes
+fn write_delta(bytes: &mut Vec<u8>, delta: u64) -> Result<(), Error> {
+    if delta > MAX_DELTA {
+        return Err(Error::InvalidMidiFile(format!("delta time of {} ticks is too large", delta)));
+    }
+    write_varlen(bytes, delta as u32);
+    Ok( () )
+}
+
+/// Returns the bytes of a meta event
+fn meta_bytes(kind: u8, data: &[u8]) -> Vec<u8> {
+    let mut res = vec![ 0xFF, kind ];
+    write_varlen(&mut res, data.len() as u32);
+    res.extend_from_slice(data);
+    res
+}
+
+/// Fails unless a channel is between 0 and 15
+fn check_channel(channel: u8) -> Result<(), Error> {
+    if channel > 15 {
+        return Err(Error::InvalidMidiFile(format!("channel {} is out of range", channel)));
+    }
+    Ok( () )
+}
+
+/// Writes the events of a track chunk. Events at the same tick are written meta events first,
+/// then program changes, note offs and note ons, consecutive channel messages sharing their
+/// status byte
+fn write_track(track: &Track) -> Result<Vec<u8>, Error> {
+    // Tick, order at that tick and bytes of each event
+    let mut events : Vec<(u64, u8, Vec<u8>)> = Vec::new();
+    if let Some(name) = &track.name {
+        events.push( (0, 0, meta_bytes(0x03, name.as_bytes())) );
+    }
+    for tempo in track.tempos.iter() {
+        if tempo.microseconds_per_quarter > 0xFF_FFFF {
+            return Err(Error::InvalidMidiFile(format!("tempo of {} microseconds per quarter note is out of range", tempo.microseconds_per_quarter)));
+        }
+        let data = tempo.microseconds_per_quarter.to_be_bytes();
+        events.push( (tempo.tick, 1, meta_bytes(0x51, &data[1..])) );
+    }
+    for time_signature in track.time_signatures.iter() {
+        if !time_signature.denominator.is_power_of_two() {
+            return Err(Error::InvalidMidiFile(format!("time signature denominator {} is not a power of 2", time_signature.denominator)));
+        }
+        let data = [
+            time_signature.numerator,
+            time_signature.denominator.trailing_zeros() as u8,
+            time_signature.clocks_per_click,
+            time_signature.thirty_seconds_per_quarter
+        ];
+        events.push( (time_signature.tick, 1, meta_bytes(0x58, &data)) );
+    }
+    for key_signature in track.key_signatures.iter() {
+        let signature = key_signature.key.get_signature();
+        if !(-7..=7).contains(&signature) {
+            return Err(Error::InvalidMidiFile(format!("key signature of {} is out of range", signature)));
+        }
+        let minor = if key_signature.key.get_quality() == KeyQuality::Minor { 1 } else { 0 };
+        let data = [ signature as i8 as u8, minor ];
+        events.push( (key_signature.tick, 1, meta_bytes(0x59, &data)) );
+    }
+    for meta_event in track.meta_events.iter() {
+        events.push( (meta_event.tick, 2, meta_bytes(meta_event.kind, &meta_event.data)) );
+    }
+    for program_change in track.program_changes.iter() {
+        check_channel(program_change.channel)?;
+        if program_change.program > 127 {
+            return Err(Error::InvalidMidiFile(format!("program {} is out of range", program_change.program)));
+        }
+        events.push( (program_change.tick, 3, vec![ 0xC0 | program_change.channel, program_change.program ]) );
+    }
+    for event in track.notes.iter() {
+        let key = event.note.get_midi_number()? as u8;
+        let channel = event.channel;
+        check_channel(channel)?;
+        if !(1..=127).contains(&event.velocity) {
+            return Err(Error::InvalidMidiFile(format!("velocity {} is out of range", event.velocity)));
+        }
+        // Notes without duration stop right after starting
+        let order = if event.duration == 0 { 6 } else { 4 };
+        events.push( (event.tick + event.duration, order, vec![ 0x80 | channel, key, 0x40 ]) );
+        events.push( (event.tick, 5, vec![ 0x90 | channel, key, event.velocity ]) );
+    }
+    events.sort_by_key(|event| (event.0, event.1));
+
+    let mut res = Vec::new();
+    let mut tick = 0;
+    let mut running_status : Option<u8> = None;
+    for (event_tick, _, bytes) in events.iter() {
+        write_delta(&mut res, event_tick - tick)?;
+        tick = *event_tick;
+        if bytes[0] < 0xF0 && running_status == Some( bytes[0] ) {
+            res.extend_from_slice(&bytes[1..]);
+        } else {
+            res.extend_from_slice(bytes);
+        }
+        running_status = if bytes[0] < 0xF0 { Some( bytes[0] ) } else { None };
+    }
+    write_delta(&mut res, track.end_tick.max(tick) - tick)?;
+    res.extend( meta_bytes(0x2F, &[]) );
+    Ok( res )
+}
+
+/// Writes a MIDI file as the bytes of a Standard MIDI File, failing if a note has no MIDI number
+/// or if a value does not fit its event e.g. a velocity of 0 or a channel above 15
+pub fn write_smf(file: &MidiFile) -> Result<Vec<u8>, Error> {
+    let mut res = Vec::new();
+    res.extend_from_slice(b"MThd");
+    res.extend_from_slice(&6u32.to_be_bytes());
+    res.extend_from_slice(&file.format.to_be_bytes());
+    res.extend_from_slice(&(file.tracks.len() as u16).to_be_bytes());
+    res.extend_from_slice(&file.ticks_per_quarter.to_be_bytes());
+    for track in file.tracks.iter() {
+        let data = write_track(track)?;
+        res.extend_from_slice(b"MTrk");
+        res.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        res.extend( data );
+    }
+    Ok( res )
+}
+
+/// Returns the notes of voicings played one after the other, each one lasting a number of ticks.
+/// Empty voicings are rests
+pub fn notes_from_voicings(voicings: &[Vec<MIDINote>], duration: u64, velocity: u8, channel: u8) -> Vec<NoteEvent> {
+    voicings.iter().enumerate()
+        .flat_map(|(idx, voicing)| voicing.iter()
+            .map(move |note| NoteEvent::new(idx as u64 * duration, duration, *note, velocity, channel)))
+        .collect()
+}
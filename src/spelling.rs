@@ -0,0 +1,49 @@
+use crate::{Note, NoteAccidental, Key, all_note_letters};
+
+/// Policy deciding how a pitch is written when several spellings are possible
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SpellingPolicy {
+    /// Natural notes when possible, sharps otherwise
+    PreferSharps,
+    /// Natural notes when possible, flats otherwise
+    PreferFlats,
+    /// Spelling used within a key signature
+    KeySignature(Key),
+    /// Spelling with the fewest accidentals, keeping the original one on ties
+    MinimiseAccidentals
+}
+
+impl SpellingPolicy {
+
+    /// Spells a note following the policy
+    pub fn spell(&self, note: &Note) -> Note {
+        match self {
+            Self::PreferSharps => spell_with_accidental(note, NoteAccidental::Sharp),
+            Self::PreferFlats => spell_with_accidental(note, NoteAccidental::Flat),
+            Self::KeySignature(key) => key.spell(note),
+            Self::MinimiseAccidentals => {
+                let mut res = *note;
+                for candidate in note.equivalents() {
+                    if candidate.get_accidental().get_offset().abs() < res.get_accidental().get_offset().abs() {
+                        res = candidate;
+                    }
+                }
+                res
+            }
+        }
+    }
+}
+
+/// Spells a note as a natural note if possible, otherwise with a sharp or a flat
+fn spell_with_accidental(note: &Note, accidental: NoteAccidental) -> Note {
+    let index = note.get_index() as i32;
+    let letters = all_note_letters();
+    if let Some(letter) = letters.iter().find(|letter| letter.get_index() as i32 == index) {
+        return Note::new(*letter, NoteAccidental::Natural);
+    }
+    let target = (index - accidental.get_offset()).rem_euclid(12);
+    match letters.iter().find(|letter| letter.get_index() as i32 == target) {
+        Some(letter) => Note::new(*letter, accidental),
+        None => unreachable!("the notes a semitone either side of a black key are natural")
+    }
+}
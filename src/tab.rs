@@ -0,0 +1,184 @@
+use crate::{MIDINote, Fretboard, FretPosition, Error};
+
+/// Characters of a tablature line which are not frets, such as hammer-ons, slides or bends
+const TAB_SYMBOLS : &str = "-hpbrv/\\~x()^.st";
+
+/// A note read from or written to tablature, its time being counted in columns from the start
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TabNote {
+    time: u32,
+    position: FretPosition,
+    note: MIDINote
+}
+
+impl TabNote {
+
+    /// Creates a tablature note at a time on a position of the fretboard
+    pub fn new(time: u32, position: FretPosition, note: MIDINote) -> TabNote {
+        TabNote{ time, position, note }
+    }
+
+    pub fn get_time(&self) -> u32 { self.time }
+    pub fn get_position(&self) -> FretPosition { self.position }
+    pub fn get_note(&self) -> MIDINote { self.note }
+}
+
+/// Splits a tablature line into its string name and its columns, bar lines being left out.
+/// Returns None for lines which are not tablature e.g. lyrics or chord names
+fn split_tab_line(line: &str) -> Option<(&str, Vec<char>)> {
+    let line = line.trim();
+    let start = line.find(['|', '-'])?;
+    let (name, rest) = line.split_at(start);
+    let name = name.trim();
+    if name.chars().count() > 3 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '#') {
+        return None;
+    }
+    let columns : Vec<char> = rest.chars().filter(|c| *c != '|').collect();
+    if !columns.contains(&'-') || !columns.iter().all(|c| c.is_ascii_digit() || TAB_SYMBOLS.contains(*c)) {
+        return None;
+    }
+    Some( (name, columns) )
+}
+
+/// Reads the notes of a block of tablature lines, the highest string first, starting at a time
+fn parse_tab_block(fretboard: &Fretboard, block: &[Vec<char>], start: u32) -> Result<Vec<TabNote>, Error> {
+    let strings = fretboard.get_string_count();
+    if block.len() != strings {
+        return Err(Error::InvalidTab(format!("expected {} strings but found {} lines", strings, block.len())));
+    }
+    let mut res = Vec::new();
+    for (line, columns) in block.iter().enumerate() {
+        let string = strings - 1 - line;
+        let mut column = 0;
+        while column < columns.len() {
+            if !columns[column].is_ascii_digit() {
+                column += 1;
+                continue;
+            }
+            let digits : String = columns[column..].iter().take_while(|c| c.is_ascii_digit()).collect();
+            let fret = digits.parse::<u32>()
+                .map_err(|_| Error::InvalidTab(format!("fret {} is too high", digits)))?;
+            let position = FretPosition::new(string, fret);
+            res.push( TabNote::new(start + column as u32, position, fretboard.get_note(&position)?) );
+            column += digits.len();
+        }
+    }
+    Ok( res )
+}
+
+/// Parses ASCII guitar tablature into notes ordered by time, then from the thickest string. Each
+/// block has one line per string of the fretboard, the highest string first, and blocks follow
+/// one another in time. String names and bar lines are optional, and playing technique symbols
+/// such as h, p, / or ~ are skipped
+pub fn parse_tab(text: &str, fretboard: &Fretboard) -> Result<Vec<TabNote>, Error> {
+    let mut res = Vec::new();
+    let mut block : Vec<Vec<char>> = Vec::new();
+    let mut time = 0;
+    for line in text.lines().chain(std::iter::once("")) {
+        match split_tab_line(line) {
+            Some((_, columns)) => block.push( columns ),
+            None if !block.is_empty() => {
+                res.extend( parse_tab_block(fretboard, &block, time)? );
+                time += block.iter().map(|columns| columns.len() as u32).max().unwrap_or(0);
+                block.clear();
+            },
+            None => {}
+        }
+    }
+    res.sort_by_key(|tab_note| (tab_note.time, tab_note.position.get_string()));
+    Ok( res )
+}
+
+/// Returns the names of the strings written before each tablature line, the highest string
+/// being in lower case when it has the same name as the lowest one e.g. e and E
+fn string_names(fretboard: &Fretboard) -> Vec<String> {
+    let strings = fretboard.get_tuning().get_strings();
+    let mut names : Vec<String> = strings.iter().map(|note| note.get_note().to_string()).collect();
+    if names.len() > 1 && names[0] == names[names.len() - 1] {
+        let last = names.len() - 1;
+        names[last] = names[last].to_lowercase();
+    }
+    let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+    names.into_iter().map(|name| format!("{:width$}", name, width = width)).collect()
+}
+
+/// Writes notes as ASCII tablature, the highest string first, each note being placed at its time
+pub fn write_tab(fretboard: &Fretboard, notes: &[TabNote]) -> String {
+    let strings = fretboard.get_string_count();
+    let length = notes.iter()
+        .map(|tab_note| tab_note.time as usize + tab_note.position.get_fret().to_string().len() + 1)
+        .max()
+        .unwrap_or(1);
+    let mut rows : Vec<Vec<char>> = vec![ vec!['-'; length]; strings ];
+    for tab_note in notes.iter().filter(|tab_note| tab_note.position.get_string() < strings) {
+        let row = &mut rows[tab_note.position.get_string()];
+        for (idx, c) in tab_note.position.get_fret().to_string().chars().enumerate() {
+            row[tab_note.time as usize + idx] = c;
+        }
+    }
+    let names = string_names(fretboard);
+    let lines : Vec<String> = (0..strings).rev()
+        .map(|string| format!("{}|{}|", names[string], rows[string].iter().collect::<String>()))
+        .collect();
+    lines.join("\n") + "\n"
+}
+
+/// Finds the positions of notes played together on distinct strings, keeping the fretted notes
+/// close to each other and to the current hand position
+fn find_positions(fretboard: &Fretboard, notes: &[MIDINote], hand: u32) -> Option<Vec<FretPosition>> {
+    let choices : Vec<Vec<FretPosition>> = notes.iter().map(|note| fretboard.get_positions(note)).collect();
+    let mut best : Option<((u32, u32), Vec<FretPosition>)> = None;
+    let mut current : Vec<FretPosition> = Vec::new();
+    search_positions(&choices, hand, &mut current, &mut best);
+    best.map(|elt| elt.1)
+}
+
+/// Tries every position of the remaining notes, keeping the cheapest complete assignment
+fn search_positions(choices: &[Vec<FretPosition>], hand: u32, current: &mut Vec<FretPosition>, best: &mut Option<((u32, u32), Vec<FretPosition>)>) {
+    if current.len() == choices.len() {
+        let fretted : Vec<u32> = current.iter().map(|position| position.get_fret()).filter(|fret| *fret > 0).collect();
+        let low = fretted.iter().min().cloned().unwrap_or(hand);
+        let high = fretted.iter().max().cloned().unwrap_or(hand);
+        let cost = ((high - low) + (low as i32 - hand as i32).unsigned_abs(), fretted.iter().sum());
+        if best.as_ref().map(|elt| cost < elt.0).unwrap_or(true) {
+            *best = Some( (cost, current.clone()) );
+        }
+        return;
+    }
+    for position in choices[current.len()].iter() {
+        if current.iter().any(|elt| elt.get_string() == position.get_string()) {
+            continue;
+        }
+        current.push( *position );
+        search_positions(choices, hand, current, best);
+        current.pop();
+    }
+}
+
+/// Renders voicings one after the other as ASCII tablature for a fretboard's tuning. The notes of
+/// each voicing are played on distinct strings near the previous ones, empty voicings being rests
+pub fn render_tab(fretboard: &Fretboard, voicings: &[Vec<MIDINote>]) -> Result<String, Error> {
+    let mut tab_notes = Vec::new();
+    let mut hand = 0;
+    let mut time = 1;
+    for voicing in voicings.iter() {
+        let positions = find_positions(fretboard, voicing, hand)
+            .ok_or_else(|| Error::UnplayableNotes(voicing.clone()))?;
+        let mut width = 1;
+        for (position, note) in positions.iter().zip(voicing.iter()) {
+            tab_notes.push( TabNote::new(time, *position, *note) );
+            width = width.max(position.get_fret().to_string().len() as u32);
+        }
+        if let Some(low) = positions.iter().map(|position| position.get_fret()).filter(|fret| *fret > 0).min() {
+            hand = low;
+        }
+        time += width + 2;
+    }
+    Ok( write_tab(fretboard, &tab_notes) )
+}
+
+/// Renders a melody as ASCII tablature for a fretboard's tuning, one note at a time
+pub fn render_melody_tab(fretboard: &Fretboard, notes: &[MIDINote]) -> Result<String, Error> {
+    let voicings : Vec<Vec<MIDINote>> = notes.iter().map(|note| vec![ *note ]).collect();
+    render_tab(fretboard, &voicings)
+}
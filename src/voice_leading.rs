@@ -0,0 +1,127 @@
+use itertools::Itertools;
+use crate::{Note, MIDINote, Interval, IntervalQuality, Chord, ChordPosition};
+use crate::voicing::place;
+
+/// Returns how readily a chord tone is left out when there are fewer voices than tones, the
+/// fifth going first and the third, seventh and alterations last
+fn tone_importance(interval: &Interval) -> u32 {
+    match (interval.get_number(), interval.get_quality()) {
+        (5, IntervalQuality::Perfect) => 0,
+        (1, _) => 1,
+        (9, IntervalQuality::Major) => 2,
+        (11, IntervalQuality::Perfect) => 3,
+        _ => 4
+    }
+}
+
+/// Returns the total number of semitones moved by the voices going from one voicing to the other,
+/// voices being paired from the lowest
+pub fn voice_leading_distance(from: &[MIDINote], to: &[MIDINote]) -> u32 {
+    from.iter().zip(to.iter())
+        .map(|(a, b)| (a.get_index() as i32 - b.get_index() as i32).unsigned_abs())
+        .sum()
+}
+
+/// Returns the number of voices which move going from one voicing to the other
+fn moving_voices(from: &[MIDINote], to: &[MIDINote]) -> usize {
+    from.iter().zip(to.iter()).filter(|(a, b)| a != b).count()
+}
+
+/// Returns every voicing of a chord with the given number of voices in ascending order between
+/// the lowest and highest notes. Every chord tone is played when there are enough voices, the
+/// least important ones being left out otherwise. Inverted and slash chords keep their lowest note in the bass
+pub fn chord_voicings(chord: &Chord, voices: usize, lowest: &MIDINote, highest: &MIDINote) -> Vec<Vec<MIDINote>> {
+    let root_note = chord.get_root();
+    let mut tones : Vec<(Interval, Note)> = chord.get_tones().into_iter()
+        .map(|interval| (interval, interval.apply(&root_note)))
+        .collect();
+    if voices == 0 || tones.is_empty() {
+        return Vec::new();
+    }
+    let fixed_bass = if chord.get_bass_note().is_some() || chord.get_position() != ChordPosition::Root {
+        Some( chord.get_lowest_note() )
+    } else {
+        None
+    };
+    // Tones which have to sound, the slash bass aside
+    let upper_voices = if chord.get_bass_note().is_some() { voices - 1 } else { voices };
+    tones.sort_by_key(|tone| std::cmp::Reverse(tone_importance(&tone.0)));
+    let required : Vec<Note> = tones.iter().take(upper_voices).map(|tone| tone.1).collect();
+
+    let low = lowest.get_index() as i32;
+    let high = highest.get_index() as i32;
+    let pitches = |notes: &[Note]| -> Vec<MIDINote> {
+        (low..=high)
+            .flat_map(|index| notes.iter()
+                .filter(move |note| (note.get_index() as i32 - index).rem_euclid(12) == 0)
+                .map(move |note| place(note, index)))
+            .collect()
+    };
+    let tone_notes : Vec<Note> = tones.iter().map(|tone| tone.1).collect();
+    let tone_pitches = pitches(&tone_notes);
+
+    let mut res : Vec<Vec<MIDINote>> = Vec::new();
+    match fixed_bass {
+        Some(bass_note) => {
+            for bass in pitches(&[bass_note]).into_iter() {
+                let upper : Vec<MIDINote> = tone_pitches.iter().filter(|note| **note > bass).cloned().collect();
+                for combination in upper.into_iter().combinations(voices - 1) {
+                    let mut voicing = vec![ bass ];
+                    voicing.extend( combination );
+                    res.push( voicing );
+                }
+            }
+        },
+        None => res.extend( tone_pitches.into_iter().combinations(voices) )
+    }
+    res.retain(|voicing| {
+        required.iter().all(|note| voicing.iter().any(|elt| elt.get_note() == *note))
+    });
+    res
+}
+
+/// Returns the voicing of a chord closest to a previous voicing with the same number of voices.
+/// Voices cannot cross, moving as few semitones as possible in total and holding common tones
+pub fn voice_lead(from: &[MIDINote], to: &Chord) -> Option<Vec<MIDINote>> {
+    let lowest = from.iter().min()?;
+    let highest = from.iter().max()?;
+    let range_low = MIDINote::from_midi_number(lowest.get_index().saturating_sub(12)).ok()?;
+    let range_high = MIDINote::from_midi_number((highest.get_index() + 12).min(127)).ok()?;
+    chord_voicings(to, from.len(), &range_low, &range_high).into_iter()
+        .min_by_key(|voicing| (voice_leading_distance(from, voicing), moving_voices(from, voicing)))
+}
+
+/// Returns the voicings of a chord progression moving the fewest semitones overall, every voicing
+/// having the given number of voices between the lowest and highest notes
+pub fn voice_lead_progression(chords: &[Chord], voices: usize, lowest: &MIDINote, highest: &MIDINote) -> Option<Vec<Vec<MIDINote>>> {
+    let candidates : Vec<Vec<Vec<MIDINote>>> = chords.iter()
+        .map(|chord| chord_voicings(chord, voices, lowest, highest))
+        .collect();
+    if candidates.is_empty() || candidates.iter().any(|voicings| voicings.is_empty()) {
+        return None;
+    }
+    // Lowest total motion reaching each voicing of the current chord, with the voicing it came from
+    let mut costs : Vec<u32> = vec![0; candidates[0].len()];
+    let mut previous : Vec<Vec<usize>> = Vec::new();
+    for pair in candidates.windows(2) {
+        let mut new_costs = Vec::new();
+        let mut links = Vec::new();
+        for voicing in pair[1].iter() {
+            let (idx, cost) = pair[0].iter().enumerate()
+                .map(|(idx, from)| (idx, costs[idx] + voice_leading_distance(from, voicing)))
+                .min_by_key(|elt| elt.1)?;
+            new_costs.push( cost );
+            links.push( idx );
+        }
+        costs = new_costs;
+        previous.push( links );
+    }
+    let mut idx = costs.iter().enumerate().min_by_key(|elt| elt.1)?.0;
+    let mut res = vec![ candidates[candidates.len() - 1][idx].clone() ];
+    for (step, links) in previous.iter().enumerate().rev() {
+        idx = links[idx];
+        res.push( candidates[step][idx].clone() );
+    }
+    res.reverse();
+    Some( res )
+}
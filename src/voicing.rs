@@ -0,0 +1,208 @@
+use std::fmt;
+use crate::{Note, MIDINote, Interval, Chord, ChordPosition};
+
+/// Ways of laying out the notes of a chord over several octaves
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum VoicingStyle {
+    /// Chord tones stacked as closely as possible, in every inversion
+    Close,
+    /// Close voicings with the second voice from the bottom raised an octave
+    Open,
+    /// Close voicings with the second voice from the top dropped an octave
+    Drop2,
+    /// Close voicings with the third voice from the top dropped an octave
+    Drop3,
+    /// Close voicings with the second and fourth voices from the top dropped an octave
+    Drop24,
+    /// Root in the bass with the other chord tones stacked an octave above it
+    Spread,
+    /// Root, third and seventh only, the sixth standing in for a missing seventh
+    Shell,
+    /// Third, fifth, seventh and ninth without the root, the thirteenth replacing the fifth of dominant chords
+    RootlessA,
+    /// Seventh, ninth, third and fifth without the root, the thirteenth replacing the fifth of dominant chords
+    RootlessB
+}
+
+pub fn all_voicing_styles() -> Vec<VoicingStyle> {
+    vec![
+        VoicingStyle::Close,
+        VoicingStyle::Open,
+        VoicingStyle::Drop2,
+        VoicingStyle::Drop3,
+        VoicingStyle::Drop24,
+        VoicingStyle::Spread,
+        VoicingStyle::Shell,
+        VoicingStyle::RootlessA,
+        VoicingStyle::RootlessB
+    ]
+}
+
+impl fmt::Display for VoicingStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Close => write!(f, "Close"),
+            Self::Open => write!(f, "Open"),
+            Self::Drop2 => write!(f, "Drop 2"),
+            Self::Drop3 => write!(f, "Drop 3"),
+            Self::Drop24 => write!(f, "Drop 2-4"),
+            Self::Spread => write!(f, "Spread"),
+            Self::Shell => write!(f, "Shell"),
+            Self::RootlessA => write!(f, "Rootless A"),
+            Self::RootlessB => write!(f, "Rootless B")
+        }
+    }
+}
+
+/// Notes of a voicing along with their distance in semitones from the lowest one
+type Shape = Vec<(Note, i32)>;
+
+/// Stacks notes upwards, each one being placed at the nearest pitch above the previous one
+fn stack(notes: &[Note]) -> Shape {
+    let mut res : Shape = Vec::new();
+    for note in notes.iter() {
+        let offset = match res.last() {
+            Some((previous, offset)) => {
+                let diff = (note.get_index() as i32 - previous.get_index() as i32).rem_euclid(12);
+                offset + if diff == 0 { 12 } else { diff }
+            },
+            None => 0
+        };
+        res.push( (*note, offset) );
+    }
+    res
+}
+
+/// Moves voices of a shape by whole octaves, voices being counted from the top starting at 1
+fn move_voices_from_top(shape: &Shape, voices: &[usize], octaves: i32) -> Option<Shape> {
+    let mut res = shape.clone();
+    for voice in voices.iter() {
+        let idx = res.len().checked_sub(*voice)?;
+        res[idx].1 += 12 * octaves;
+    }
+    Some( normalise(res) )
+}
+
+/// Sorts a shape from its lowest note and measures it from there
+fn normalise(mut shape: Shape) -> Shape {
+    shape.sort_by_key(|elt| elt.1);
+    let lowest = shape.first().map(|elt| elt.1).unwrap_or(0);
+    shape.into_iter().map(|(note, offset)| (note, offset - lowest)).collect()
+}
+
+/// Returns the chord tone of the given number if the chord has one
+fn tone_of_number(chord: &Chord, number: u32) -> Option<Interval> {
+    chord.get_tones().into_iter().find(|interval| interval.get_number() == number)
+}
+
+/// Returns the shapes of a chord for a voicing style, from which MIDI notes are placed
+fn chord_shapes(chord: &Chord, style: VoicingStyle) -> Vec<Shape> {
+    let root_note = chord.get_root();
+    let tones = Chord::with_modifiers( root_note, chord.get_quality(), &chord.get_modifiers(), ChordPosition::Root ).get_notes();
+    let rotations : Vec<Vec<Note>> = (0..tones.len())
+        .map(|i| tones.iter().cycle().skip(i).take(tones.len()).cloned().collect())
+        .collect();
+    let close_shapes : Vec<Shape> = rotations.iter().map(|notes| stack(notes)).collect();
+    let apply = |interval: Interval| interval.apply(&root_note);
+    match style {
+        VoicingStyle::Close => close_shapes,
+        VoicingStyle::Open if tones.len() >= 3 => close_shapes.iter()
+            .filter_map(|shape| move_voices_from_top(shape, &[shape.len() - 1], 1))
+            .collect(),
+        VoicingStyle::Drop2 if tones.len() >= 3 => close_shapes.iter()
+            .filter_map(|shape| move_voices_from_top(shape, &[2], -1))
+            .collect(),
+        VoicingStyle::Drop3 if tones.len() >= 4 => close_shapes.iter()
+            .filter_map(|shape| move_voices_from_top(shape, &[3], -1))
+            .collect(),
+        VoicingStyle::Drop24 if tones.len() >= 4 => close_shapes.iter()
+            .filter_map(|shape| move_voices_from_top(shape, &[2, 4], -1))
+            .collect(),
+        VoicingStyle::Spread if tones.len() >= 2 => {
+            let upper = stack(&tones[1..]);
+            let lowest_offset = 12 + (tones[1].get_index() as i32 - root_note.get_index() as i32).rem_euclid(12);
+            let mut shape = vec![ (root_note, 0) ];
+            shape.extend( upper.into_iter().map(|(note, offset)| (note, offset + lowest_offset)) );
+            vec![ shape ]
+        },
+        VoicingStyle::Shell => {
+            let third = tone_of_number(chord, 3);
+            let seventh = tone_of_number(chord, 7).or_else(|| tone_of_number(chord, 6));
+            match (third, seventh) {
+                (Some(third), Some(seventh)) => vec![
+                    stack(&[root_note, apply(third), apply(seventh)]),
+                    stack(&[root_note, apply(seventh), apply(third)])
+                ],
+                _ => Vec::new()
+            }
+        },
+        VoicingStyle::RootlessA | VoicingStyle::RootlessB => {
+            let third = tone_of_number(chord, 3);
+            let seventh = tone_of_number(chord, 7);
+            let ninth = tone_of_number(chord, 9).unwrap_or_else(|| Interval::from_name("M9").unwrap());
+            let dominant = third == Interval::from_name("M3").ok() && seventh == Interval::from_name("m7").ok();
+            let fifth = match tone_of_number(chord, 5) {
+                Some(fifth) if dominant && fifth.get_value() == 7 => Some( tone_of_number(chord, 13).unwrap_or_else(|| Interval::from_name("M13").unwrap()) ),
+                Some(fifth) => Some( fifth ),
+                None if dominant => Some( tone_of_number(chord, 13).unwrap_or_else(|| Interval::from_name("M13").unwrap()) ),
+                None => None
+            };
+            match (third, fifth, seventh) {
+                (Some(third), Some(fifth), Some(seventh)) => {
+                    let notes = if style == VoicingStyle::RootlessA {
+                        [apply(third), apply(fifth), apply(seventh), apply(ninth)]
+                    } else {
+                        [apply(seventh), apply(ninth), apply(third), apply(fifth)]
+                    };
+                    vec![ stack(&notes) ]
+                },
+                _ => Vec::new()
+            }
+        },
+        _ => Vec::new()
+    }
+}
+
+/// Returns the MIDI note of the given pitch spelled as the note, the pitch being a valid MIDI index
+pub(crate) fn place(note: &Note, index: i32) -> MIDINote {
+    let octave = (index - note.get_offset_index()).div_euclid(12) - 1;
+    MIDINote::from_note(*note, octave).expect("notes are placed from C-1 up")
+}
+
+/// Returns the voicings of a chord in a style with every note between the lowest and highest notes
+/// included, from the lowest voicing. A slash chord's bass note is placed below each voicing. An
+/// inverted chord keeps the voicings with its lowest note in the bass, that note being placed below
+/// when the style has none
+pub fn voice_chord(chord: &Chord, style: VoicingStyle, lowest: &MIDINote, highest: &MIDINote) -> Vec<Vec<MIDINote>> {
+    let mut shapes = chord_shapes(chord, style);
+    let mut bass_note = chord.get_bass_note();
+    if bass_note.is_none() && chord.get_position() != ChordPosition::Root {
+        let lowest_note = chord.get_lowest_note();
+        if shapes.iter().any(|shape| shape[0].0 == lowest_note) {
+            shapes.retain(|shape| shape[0].0 == lowest_note);
+        } else {
+            bass_note = Some( lowest_note );
+        }
+    }
+    if let Some(bass_note) = bass_note {
+        for shape in shapes.iter_mut() {
+            let below = (shape[0].0.get_index() as i32 - bass_note.get_index() as i32).rem_euclid(12);
+            shape.insert(0, (bass_note, -if below == 0 { 12 } else { below }));
+            *shape = normalise(shape.clone());
+        }
+    }
+    let low = lowest.get_index() as i32;
+    let high = highest.get_index() as i32;
+    let mut res : Vec<Vec<MIDINote>> = Vec::new();
+    for shape in shapes.iter() {
+        let span = shape[shape.len() - 1].1;
+        let first_index = shape[0].0.get_index() as i32;
+        let mut start = low + (first_index - low).rem_euclid(12);
+        while start + span <= high {
+            res.push( shape.iter().map(|(note, offset)| place(note, start + offset)).collect() );
+            start += 12;
+        }
+    }
+    res.sort_by_key(|voicing| voicing[0].get_index());
+    res
+}
@@ -1,7 +1,7 @@
 extern crate musicmaster;
 
 use std::convert::TryFrom;
-use musicmaster::{Note, NoteLetter, Chord, NoteAccidental, ChordQuality, ChordPosition};
+use musicmaster::{Note, NoteLetter, Chord, NoteAccidental, ChordQuality, ChordPosition, ChordModifier, Interval, Error, all_note_letters, all_chord_qualities};
 
 #[test]
 fn chord_string() {
@@ -33,4 +33,236 @@ fn chord_identify() {
     for chord in Chord::identify( &notes ).into_iter() {
         assert_eq!( chord, Chord::new( Note::new(NoteLetter::C, NoteAccidental::Natural), ChordQuality::DiminishedSeventh, ChordPosition::Root ) );
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn chord_unknown_shape() {
+    let intervals = vec![ Interval::new(1), Interval::new(1) ];
+    match Chord::from_intervals( Note::try_from("C").unwrap(), &intervals ) {
+        Err(Error::UnknownChordShape(shape)) => assert_eq!(shape, intervals),
+        _ => panic!("Expected an unknown chord shape")
+    }
+    assert!(ChordQuality::try_from(&intervals).is_err());
+}
+
+#[test]
+fn chord_tertian_spelling() {
+    let chord = Chord::new( Note::try_from("Eb").unwrap(), ChordQuality::Minor, ChordPosition::Root );
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["Eb","Gb","Bb"]);
+    let chord = Chord::new( Note::try_from("C").unwrap(), ChordQuality::DiminishedSeventh, ChordPosition::FirstInversion );
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["Eb","Gb","Bbb","C"]);
+    let chord = Chord::new( Note::try_from("G#").unwrap(), ChordQuality::AugmentedSeventh, ChordPosition::Root );
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["G#","B#","D##","F#"]);
+
+    let tertian = [ ChordQuality::Major, ChordQuality::Minor, ChordQuality::Diminished, ChordQuality::Augmented,
+        ChordQuality::DominantSeventh, ChordQuality::MajorSeventh, ChordQuality::MinorSeventh, ChordQuality::DiminishedSeventh,
+        ChordQuality::MinorSeventhFlatFive, ChordQuality::MinorMajorSeventh, ChordQuality::AugmentedMajorSeventh ];
+    for letter in all_note_letters() {
+        for accidental in [NoteAccidental::Flat, NoteAccidental::Natural, NoteAccidental::Sharp].iter() {
+            for quality in tertian.iter() {
+                let root = Note::new(letter, *accidental);
+                if quality.get_intervals().iter().any(|interval| interval.try_apply(&root).is_err()) {
+                    continue;
+                }
+                let chord = Chord::new( root, *quality, ChordPosition::Root );
+                let notes = chord.get_notes();
+                for w in notes.windows(2) {
+                    assert_eq!( (w[1].get_letter().get_step() + 7 - w[0].get_letter().get_step()) % 7, 2, "{}", chord );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn chord_symbol_parsing() {
+    let chord : Chord = "Cmaj7".parse().unwrap();
+    assert_eq!(chord.get_root(), Note::try_from("C").unwrap());
+    assert_eq!(chord.get_quality(), ChordQuality::MajorSeventh);
+    let chord : Chord = "F#m7b5".parse().unwrap();
+    assert_eq!(chord.get_root(), Note::try_from("F#").unwrap());
+    assert_eq!(chord.get_quality(), ChordQuality::MinorSeventhFlatFive);
+    assert_eq!(Chord::try_from("Bbø7").unwrap().get_quality(), ChordQuality::MinorSeventhFlatFive);
+    assert_eq!(Chord::try_from("Ebdim7").unwrap().get_quality(), ChordQuality::DiminishedSeventh);
+    assert_eq!(Chord::try_from("C°").unwrap().get_quality(), ChordQuality::Diminished);
+    assert_eq!(Chord::try_from("A-7").unwrap().get_quality(), ChordQuality::MinorSeventh);
+    assert_eq!(Chord::try_from("Amin").unwrap().get_quality(), ChordQuality::Minor);
+    assert_eq!(Chord::try_from("GΔ").unwrap().get_quality(), ChordQuality::MajorSeventh);
+    assert_eq!(Chord::try_from("Gaug").unwrap().get_quality(), ChordQuality::Augmented);
+    assert_eq!(Chord::try_from("G7#5").unwrap().get_quality(), ChordQuality::AugmentedSeventh);
+    assert_eq!(Chord::try_from("Dsus").unwrap().get_quality(), ChordQuality::SuspendedFour);
+    assert_eq!(Chord::try_from("Cm(maj7)").unwrap().get_quality(), ChordQuality::MinorMajorSeventh);
+    assert_eq!(Chord::try_from("D♭7(♭5)").unwrap().get_quality(), ChordQuality::DominantSeventhFlatFive);
+    assert_eq!(Chord::try_from("E").unwrap().get_quality(), ChordQuality::Major);
+    assert_eq!(Chord::try_from("C2").unwrap().get_quality(), ChordQuality::SuspendedTwo);
+    assert_eq!(Chord::try_from("C5").unwrap().get_quality(), ChordQuality::Power);
+
+    let chord = Chord::try_from("Am/C").unwrap();
+    assert_eq!(chord.get_quality(), ChordQuality::Minor);
+    assert_eq!(chord.get_notes()[0], Note::try_from("C").unwrap());
+    assert_eq!(chord.to_string(), "Am/C");
+    let chord = Chord::try_from("G7/F").unwrap();
+    assert_eq!(chord.get_notes()[0], Note::try_from("F").unwrap());
+
+    let chord = Chord::try_from("C7sus4").unwrap();
+    assert_eq!(chord.get_quality(), ChordQuality::DominantSeventh);
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["C","F","G","Bb"]);
+    for symbol in ["C7sus4", "C9sus4", "C7sus2", "C5", "C13sus4(b9)", "C6sus2"].iter() {
+        assert_eq!(Chord::try_from(*symbol).unwrap().to_string(), *symbol);
+    }
+
+    assert!(matches!(Chord::try_from("Cfoo"), Err(Error::UnknownName { .. })));
+    assert!(matches!(Chord::try_from("H7"), Err(Error::ParseNote(_))));
+
+    for quality in all_chord_qualities() {
+        let chord = Chord::new(Note::try_from("Bb").unwrap(), quality, ChordPosition::Root);
+        assert_eq!(Chord::try_from(chord.to_string().as_str()).unwrap().get_quality(), quality, "{}", chord);
+    }
+}
+
+#[test]
+fn chord_extended() {
+    let chord = Chord::try_from("Cmaj9").unwrap();
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["C","E","G","B","D"]);
+    let chord = Chord::try_from("F13").unwrap();
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["F","A","C","Eb","G","Bb","D"]);
+    let chord = Chord::try_from("Dm11/C").unwrap();
+    assert_eq!(chord.get_quality(), ChordQuality::MinorEleventh);
+    assert_eq!(chord.to_string(), "Dm11/C");
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["C","E","G","D","F","A"]);
+
+    let notes : Vec<Note> = ["Bb","D","F","Ab","C"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    let names : Vec<String> = Chord::identify(&notes).iter().map(|c| c.to_string()).collect();
+    assert_eq!(names, vec!["Bb9","Dm7(b5)/Bb","Fm6/Bb"]);
+    let notes : Vec<Note> = ["E","G","B","D","F#","A"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    assert_eq!(Chord::identify(&notes)[0].to_string(), "Em11");
+    let notes : Vec<Note> = ["A","C","E","G","B","D"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    assert_eq!(Chord::identify(&notes)[0].to_string(), "Am11");
+    let notes : Vec<Note> = ["B","D","F","A","C","E","G"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    // Every note of a diatonic scale stacked in thirds fits several thirteenth chords
+    let names : Vec<String> = Chord::identify(&notes).iter().map(|c| c.to_string()).collect();
+    assert!(names.contains(&"CMaj13/B".to_string()));
+    assert!(names.contains(&"G13/B".to_string()));
+
+    let intervals : Vec<Interval> = ["M3","m3","m3","M3","m3"].iter().map(|s| Interval::from_name(s).unwrap()).collect();
+    assert!(ChordQuality::try_from(&intervals).is_err());
+    let intervals : Vec<Interval> = ["M3","m3","m3","M3","m7"].iter().map(|s| Interval::from_name(s).unwrap()).collect();
+    assert_eq!(ChordQuality::try_from(&intervals).unwrap(), ChordQuality::DominantNinth);
+}
+
+#[test]
+fn chord_modifiers() {
+    let names = |chord: &Chord| chord.get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>();
+
+    let chord = Chord::try_from("C7b9").unwrap();
+    assert_eq!(chord.get_quality(), ChordQuality::DominantSeventh);
+    assert_eq!(chord.get_modifiers(), vec![ChordModifier::Alter(Interval::from_name("m9").unwrap())]);
+    assert_eq!(names(&chord), vec!["C","E","G","Bb","Db"]);
+    assert_eq!(chord.to_string(), "C7(b9)");
+    assert_eq!(names(&Chord::try_from("G7#9").unwrap()), vec!["G","B","D","F","A#"]);
+    assert_eq!(names(&Chord::try_from("D7(#11)").unwrap()), vec!["D","F#","A","C","G#"]);
+    assert_eq!(names(&Chord::try_from("E7b13").unwrap()), vec!["E","G#","B","D","C"]);
+    assert_eq!(names(&Chord::try_from("C7alt").unwrap()), vec!["C","E","Gb","G#","Bb","Db","D#"]);
+    assert_eq!(Chord::try_from("C7alt").unwrap().to_string(), "C7alt");
+    assert_eq!(names(&Chord::try_from("Cadd9").unwrap()), vec!["C","E","G","D"]);
+    assert_eq!(names(&Chord::try_from("F6").unwrap()), vec!["F","A","C","D"]);
+    assert_eq!(names(&Chord::try_from("Am6").unwrap()), vec!["A","C","E","F#"]);
+    assert_eq!(names(&Chord::try_from("C6/9").unwrap()), vec!["C","E","G","A","D"]);
+    assert_eq!(names(&Chord::try_from("C13(b9)").unwrap()), vec!["C","E","G","Bb","Db","F","A"]);
+    assert_eq!(names(&Chord::try_from("C7(no5)").unwrap()), vec!["C","E","Bb"]);
+    assert_eq!(names(&Chord::try_from("C9omit3").unwrap()), vec!["C","G","Bb","D"]);
+    assert_eq!(Chord::try_from("C6/9/E").unwrap().to_string(), "C6/9/E");
+
+    for symbol in ["C6", "Cm6", "C6/9", "Cadd9", "Cmadd9", "C7(b9)", "C7(#9)", "C7(#11)", "C7(b13)", "C7alt", "C9(#11)", "CMaj7(#11)", "C7(b5)(b9)", "C7(no3)", "Bb6/D"].iter() {
+        assert_eq!(Chord::try_from(*symbol).unwrap().to_string(), *symbol);
+    }
+    assert!(Chord::try_from("C7(x9)").is_err());
+    assert!(Chord::try_from("Cadd").is_err());
+
+    let chord = Chord::with_modifiers(Note::try_from("Bb").unwrap(), ChordQuality::Minor, &[ChordModifier::Add(Interval::from_name("M6").unwrap())], ChordPosition::Root);
+    assert_eq!(chord.to_string(), "Bbm6");
+
+    let notes : Vec<Note> = ["C","E","G","Bb","D#"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    let identified : Vec<String> = Chord::identify(&notes).iter().map(|c| c.to_string()).collect();
+    assert_eq!(identified, vec!["C7(#9)"]);
+    let notes : Vec<Note> = ["C","E","G","A"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    let identified : Vec<String> = Chord::identify(&notes).iter().map(|c| c.to_string()).collect();
+    assert_eq!(identified, vec!["Am7/C", "C6"]);
+    let notes : Vec<Note> = ["E","G","D","C"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    let identified : Vec<String> = Chord::identify(&notes).iter().map(|c| c.to_string()).collect();
+    assert_eq!(identified, vec!["Cadd9/E"]);
+}
+
+#[test]
+fn chord_slash() {
+    let chord = Chord::try_from("C/D").unwrap();
+    assert_eq!(chord.get_quality(), ChordQuality::Major);
+    assert_eq!(chord.get_position(), ChordPosition::Root);
+    assert_eq!(chord.get_bass_note(), Some(Note::try_from("D").unwrap()));
+    let names : Vec<String> = chord.get_notes().iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["D","C","E","G"]);
+    assert_eq!(chord.to_string(), "C/D");
+    assert_eq!(Chord::try_from("Am/G").unwrap().to_string(), "Am/G");
+    assert_eq!(Chord::try_from("Fmaj7/G").unwrap().to_string(), "FMaj7/G");
+
+    // A bass note belonging to the chord is an inversion
+    let chord = Chord::try_from("Am/C").unwrap();
+    assert_eq!(chord.get_bass_note(), None);
+    assert_eq!(chord.get_position(), ChordPosition::FirstInversion);
+    let chord = Chord::new(Note::try_from("F").unwrap(), ChordQuality::Major, ChordPosition::Root);
+    assert_eq!(chord.with_bass(&Note::try_from("C").unwrap()).get_position(), ChordPosition::SecondInversion);
+    assert_eq!(chord.with_bass(&Note::try_from("G").unwrap()).to_string(), "F/G");
+
+    let identify = |names: &[&str]| {
+        let notes : Vec<Note> = names.iter().map(|s| Note::try_from(*s).unwrap()).collect();
+        Chord::identify(&notes).iter().map(|c| c.to_string()).collect::<Vec<String>>()
+    };
+    assert!(identify(&["G","A","C","E"]).contains(&"Am/G".to_string()));
+    assert_eq!(identify(&["D","C","E","G"]), vec!["Cadd9/D", "C/D"]);
+    assert_eq!(identify(&["G","F","A","C"]), vec!["Fadd9/G", "F/G"]);
+    assert_eq!(identify(&["Eb","C","E","G"]), vec!["C/Eb"]);
+    // Doubled notes are only read as a pitch set
+    assert!(identify(&["C","C","E","G"]).is_empty());
+    let notes : Vec<Note> = ["C","C","E","G"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    assert_eq!(Chord::identify_pitch_set(&notes)[0].to_string(), "C");
+}
+
+#[test]
+fn chord_identify_pitch_set() {
+    let identify = |names: &[&str]| {
+        let notes : Vec<Note> = names.iter().map(|s| Note::try_from(*s).unwrap()).collect();
+        Chord::identify_pitch_set(&notes).iter().map(|c| c.to_string()).collect::<Vec<String>>()
+    };
+    assert_eq!(identify(&["C","G","E"]), vec!["C"]);
+    assert_eq!(identify(&["E","C","G"]), vec!["C/E"]);
+    assert_eq!(identify(&["C","E","G","C","E"]), vec!["C"]);
+    assert_eq!(identify(&["G","Bb","E","C"]), vec!["C7/G", "Bbsus2(b5)/G"]);
+    assert_eq!(identify(&["A","G","C","E"]), vec!["Am7", "C6/A", "C/A"]);
+    assert_eq!(identify(&["C","G","D"]), vec!["Csus2", "Gsus4/C"]);
+    assert_eq!(identify(&["D","E","D","G","C"]), vec!["Cadd9/D", "C/D"]);
+    assert!(identify(&["C","C#","D"]).is_empty());
+    assert!(identify(&[]).is_empty());
+
+    let notes : Vec<Note> = ["Bb","E","G","D","C"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    let chords = Chord::identify_pitch_set(&notes);
+    assert_eq!(chords[0].get_root(), Note::try_from("C").unwrap());
+    assert_eq!(chords[0].get_quality(), ChordQuality::DominantNinth);
+    assert_eq!(chords[0].get_lowest_note(), Note::try_from("Bb").unwrap());
+    assert_eq!(chords[0].get_position(), ChordPosition::ThirdInversion);
+
+    // Every note of a diminished seventh chord can be its root
+    let notes : Vec<Note> = ["C","Eb","Gb","A"].iter().map(|s| Note::try_from(*s).unwrap()).collect();
+    let chords : Vec<Chord> = Chord::identify_pitch_set(&notes).into_iter()
+        .filter(|chord| chord.get_bass_note().is_none())
+        .collect();
+    assert_eq!(chords.len(), 4);
+    assert!(chords.iter().all(|chord| chord.get_quality() == ChordQuality::DiminishedSeventh));
+    assert_eq!(chords[0].to_string(), "Cº7");
+}
@@ -0,0 +1,59 @@
+extern crate musicmaster;
+
+use std::convert::TryFrom;
+use musicmaster::{Key, KeyQuality, Note, NoteLetter, Error};
+use musicmaster::{fifths_position, circle_position, key_from_signature, step_clockwise, step_counter_clockwise, circle_distance, closely_related_keys, enharmonic_key};
+
+fn major(name: &str) -> Key {
+    Key::new(Note::try_from(name).unwrap(), KeyQuality::Major)
+}
+
+#[test]
+fn circle_positions() {
+    assert_eq!(fifths_position(&Note::try_from("G").unwrap()), 1);
+    assert_eq!(fifths_position(&Note::try_from("Bb").unwrap()), -2);
+    assert_eq!(fifths_position(&Note::try_from("F#").unwrap()), 6);
+    assert_eq!(fifths_position(&Note::try_from("Gb").unwrap()), -6);
+    assert_eq!(circle_position(&Note::try_from("F#").unwrap()), 6);
+    assert_eq!(circle_position(&Note::try_from("Gb").unwrap()), 6);
+    assert_eq!(circle_position(&Note::try_from("F").unwrap()), 11);
+    assert_eq!(key_from_signature(-3, KeyQuality::Minor).unwrap().to_string(), String::from("C minor"));
+    assert_eq!(key_from_signature(5, KeyQuality::Major).unwrap().to_string(), String::from("B major"));
+    assert_eq!(key_from_signature(19, KeyQuality::Major).unwrap().to_string(), String::from("B## major"));
+    assert_eq!(key_from_signature(20, KeyQuality::Major), Err(Error::UnspellableNote { letter: NoteLetter::F, offset: 3 }));
+}
+
+#[test]
+fn circle_stepping() {
+    assert_eq!(step_clockwise(&major("C"), 2).to_string(), String::from("D major"));
+    assert_eq!(step_clockwise(&major("B"), 1).to_string(), String::from("F# major"));
+    assert_eq!(step_clockwise(&major("B"), 2).to_string(), String::from("Db major"));
+    assert_eq!(step_counter_clockwise(&major("Db"), 1).to_string(), String::from("Gb major"));
+    assert_eq!(step_counter_clockwise(&major("Db"), 2).to_string(), String::from("B major"));
+    assert_eq!(step_clockwise(&major("C"), 12).to_string(), String::from("C major"));
+    assert_eq!(enharmonic_key(&major("F#")).to_string(), String::from("Gb major"));
+    assert_eq!(enharmonic_key(&major("Cb")).to_string(), String::from("B major"));
+}
+
+#[test]
+fn circle_distances() {
+    assert_eq!(circle_distance(&major("C"), &major("A")), 3);
+    assert_eq!(circle_distance(&major("C"), &major("Eb")), -3);
+    assert_eq!(circle_distance(&major("F#"), &major("Gb")), 0);
+    assert_eq!(circle_distance(&major("B"), &major("Db")), 2);
+    let a_minor = Key::new(Note::try_from("A").unwrap(), KeyQuality::Minor);
+    assert_eq!(circle_distance(&major("C"), &a_minor), 0);
+}
+
+#[test]
+fn circle_closely_related() {
+    let related : Vec<String> = closely_related_keys(&major("G")).iter().map(|key| key.to_string()).collect();
+    assert_eq!(related, vec!["E minor","D major","B minor","C major","A minor"]);
+    let d_minor = Key::new(Note::try_from("D").unwrap(), KeyQuality::Minor);
+    let related : Vec<String> = closely_related_keys(&d_minor).iter().map(|key| key.to_string()).collect();
+    assert_eq!(related, vec!["F major","A minor","C major","G minor","Bb major"]);
+    // B## major has no dominant key
+    let related = closely_related_keys(&major("B##"));
+    assert_eq!(related.len(), 3);
+    assert_eq!(related[1], major("E##"));
+}
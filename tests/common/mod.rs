@@ -0,0 +1,24 @@
+//! Helpers shared by the integration tests, each test crate using some of them
+#![allow(dead_code)]
+
+use musicmaster::{MIDINote, Tuning, Fretboard};
+
+/// Parses a MIDI note name e.g. C4
+pub fn midi(name: &str) -> MIDINote {
+    name.parse::<MIDINote>().unwrap()
+}
+
+/// Parses MIDI note names
+pub fn midi_notes(names: &[&str]) -> Vec<MIDINote> {
+    names.iter().map(|name| midi(name)).collect()
+}
+
+/// Returns the names of MIDI notes
+pub fn names(notes: &[MIDINote]) -> Vec<String> {
+    notes.iter().map(|note| note.to_string()).collect()
+}
+
+/// Returns a guitar fretboard in standard tuning with 22 frets
+pub fn fretboard() -> Fretboard {
+    Fretboard::new(Tuning::standard(), 22)
+}
@@ -0,0 +1,91 @@
+extern crate musicmaster;
+
+mod common;
+
+use std::convert::TryFrom;
+use musicmaster::{Note, Chord, Mode, Scale, Tuning, Fretboard, Fingering, chord_box, fretboard_svg, keyboard_svg, keyboard_svg_for_notes};
+use common::midi;
+
+fn fingering(frets: &str) -> Fingering {
+    Fingering::new( frets.chars().map(|c| c.to_digit(10)).collect() )
+}
+
+#[test]
+fn diagram_chord_box() {
+    assert_eq!(chord_box(&fingering("x32010")), "\
+x     o   o
+===========
+| | | | O |
+| | O | | |
+| O | | | |
+| | | | | |
+");
+    assert_eq!(chord_box(&fingering("133211")), "\n\
+===========
+O-O-O-O-O-O
+| | | O | |
+| O O | | |
+| | | | | |
+");
+    assert_eq!(chord_box(&Fingering::new(vec![Some(5), Some(7), Some(7), Some(6), Some(5), Some(5)])), "\n\
+O-O-O-O-O-O 5fr
+| | | O | |
+| O O | | |
+| | | | | |
+");
+    assert_eq!(chord_box(&fingering("0003")), "\
+o o o
+=======
+| | | |
+| | | |
+| | | O
+| | | |
+");
+    assert_eq!(chord_box(&Fingering::new(Vec::new())), "");
+}
+
+#[test]
+fn diagram_fretboard_svg() {
+    let ukulele = Fretboard::new(Tuning::ukulele(), 12);
+    let svg = fretboard_svg(&ukulele, &fingering("0003").get_positions(&ukulele), 0, 3);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"180\" height=\"120\" viewBox=\"0 0 180 120\">\n"));
+    assert!(svg.ends_with("</svg>\n"));
+    assert!(svg.contains("<line x1=\"30\" y1=\"30\" x2=\"30\" y2=\"90\" stroke=\"black\" stroke-width=\"4\"/>\n"));
+    assert!(svg.contains("<circle cx=\"15\" cy=\"90\" r=\"8\" fill=\"#3366cc\"/>\n<text x=\"15\" y=\"93\" font-size=\"9\" text-anchor=\"middle\" fill=\"white\">G</text>\n"));
+    assert!(svg.contains("<circle cx=\"130\" cy=\"30\" r=\"8\" fill=\"#3366cc\"/>\n<text x=\"130\" y=\"33\" font-size=\"9\" text-anchor=\"middle\" fill=\"white\">C</text>\n"));
+    assert_eq!(svg.matches("<circle").count(), 4);
+
+    let guitar = Fretboard::new(Tuning::standard(), 22);
+    let mode = Mode::new(Note::try_from("C").unwrap(), Scale::Major, 6);
+    let positions = guitar.find_mode(&mode, 5, 8);
+    let svg = fretboard_svg(&guitar, &positions, 5, 8);
+    assert_eq!(svg.matches("<circle").count(), positions.len());
+    assert!(!svg.contains("stroke-width=\"4\""));
+    assert!(svg.contains(">5</text>"));
+
+    let no_strings = Fretboard::new(Tuning::new(Vec::new()), 12);
+    assert_eq!(fretboard_svg(&no_strings, &[], 0, 3),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"0\" height=\"0\" viewBox=\"0 0 0 0\">\n</svg>\n");
+}
+
+#[test]
+fn diagram_keyboard_svg() {
+    let notes = vec![ midi("C4"), midi("E4"), midi("G4") ];
+    assert_eq!(keyboard_svg(&notes, &midi("C4"), &midi("E4")), "\
+<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"60\" height=\"100\" viewBox=\"0 0 60 100\">
+<rect x=\"0\" y=\"0\" width=\"20\" height=\"100\" fill=\"#3366cc\" stroke=\"black\"/>
+<text x=\"10\" y=\"94\" font-size=\"9\" text-anchor=\"middle\" fill=\"white\">C</text>
+<rect x=\"20\" y=\"0\" width=\"20\" height=\"100\" fill=\"white\" stroke=\"black\"/>
+<rect x=\"40\" y=\"0\" width=\"20\" height=\"100\" fill=\"#3366cc\" stroke=\"black\"/>
+<text x=\"50\" y=\"94\" font-size=\"9\" text-anchor=\"middle\" fill=\"white\">E</text>
+<rect x=\"14\" y=\"0\" width=\"12\" height=\"60\" fill=\"black\" stroke=\"black\"/>
+<rect x=\"34\" y=\"0\" width=\"12\" height=\"60\" fill=\"black\" stroke=\"black\"/>
+</svg>
+");
+
+    // Black keys at the ends are widened to white keys
+    let svg = keyboard_svg_for_notes(&Chord::try_from("D").unwrap().get_notes(), &midi("C#4"), &midi("C#5"));
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"180\" height=\"100\" viewBox=\"0 0 180 100\">\n"));
+    assert_eq!(svg.matches("fill=\"#3366cc\"").count(), 4);
+    assert!(svg.contains(">F#</text>"));
+}
@@ -0,0 +1,66 @@
+extern crate musicmaster;
+
+mod common;
+
+use std::convert::TryFrom;
+use musicmaster::{Chord, Tuning, Fretboard, Fingering, find_fingerings};
+use common::{fretboard, midi_notes};
+
+#[test]
+fn fingering_open_chords() {
+    let fingerings = find_fingerings(&fretboard(), &Chord::try_from("C").unwrap(), 5, 3);
+    assert_eq!(fingerings[0].to_string(), "x32010");
+    assert_eq!(fingerings[0].get_finger_count(), 3);
+    assert_eq!(fingerings[0].get_muted_count(), 1);
+    assert_eq!(fingerings[0].get_open_count(), 2);
+    assert!(fingerings[0].get_barre().is_none());
+    assert!(fingerings.windows(2).all(|w| w[0].get_difficulty() <= w[1].get_difficulty()));
+
+    assert_eq!(find_fingerings(&fretboard(), &Chord::try_from("G").unwrap(), 5, 3)[0].to_string(), "320003");
+    assert_eq!(find_fingerings(&fretboard(), &Chord::try_from("Em").unwrap(), 5, 3)[0].to_string(), "022000");
+    assert_eq!(find_fingerings(&fretboard(), &Chord::try_from("D").unwrap(), 5, 3)[0].to_string(), "xx0232");
+}
+
+#[test]
+fn fingering_barre_chords() {
+    let fingering = Fingering::new(vec![Some(1), Some(3), Some(3), Some(2), Some(1), Some(1)]);
+    let barre = fingering.get_barre().unwrap();
+    assert_eq!((barre.get_fret(), barre.get_first_string(), barre.get_last_string()), (1, 0, 5));
+    assert_eq!(fingering.get_finger_count(), 4);
+    assert_eq!(fingering.get_span(), 2);
+
+    let fingerings = find_fingerings(&fretboard(), &Chord::try_from("F").unwrap(), 5, 3);
+    assert!(fingerings.iter().any(|f| f.to_string() == "133211"));
+    let fingerings = find_fingerings(&fretboard(), &Chord::try_from("Bm").unwrap(), 9, 3);
+    assert!(fingerings.iter().any(|f| f.to_string() == "x24432" && f.get_barre().is_some()));
+}
+
+#[test]
+fn fingering_bass_and_notes() {
+    let board = fretboard();
+    // Every fingering of a slash chord has the bass note lowest
+    let chord = Chord::try_from("C/G").unwrap();
+    for fingering in find_fingerings(&board, &chord, 5, 3) {
+        let notes = fingering.get_notes(&board);
+        assert_eq!(notes.iter().min().unwrap().get_note(), chord.get_lowest_note(), "{}", fingering);
+        assert!(fingering.get_finger_count() <= 4);
+        assert!(fingering.get_span() <= 3);
+    }
+    let fingering = Fingering::new(vec![None, Some(3), Some(2), Some(0), Some(1), Some(0)]);
+    let names : Vec<String> = fingering.get_notes(&board).iter().map(|n| n.to_string()).collect();
+    assert_eq!(names, vec!["C3","E3","G3","C4","E4"]);
+    assert_eq!(Fingering::new(vec![Some(10), Some(12), Some(12), Some(11), Some(10), Some(10)]).to_string(), "10-12-12-11-10-10");
+}
+
+#[test]
+fn fingering_above_midi_range() {
+    let board = Fretboard::new(Tuning::new(midi_notes(&["C9", "E9", "G9"])), 24);
+    let fingerings = find_fingerings(&board, &Chord::try_from("C").unwrap(), 24, 3);
+    assert_eq!(fingerings.iter().map(|f| f.to_string()).collect::<Vec<String>>(), vec!["000"]);
+}
+
+#[test]
+fn fingering_without_strings() {
+    let board = Fretboard::new(Tuning::new(Vec::new()), 24);
+    assert!(find_fingerings(&board, &Chord::try_from("C").unwrap(), 5, 3).is_empty());
+}
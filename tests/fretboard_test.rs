@@ -0,0 +1,56 @@
+extern crate musicmaster;
+
+use std::convert::TryFrom;
+use musicmaster::{Note, MIDINote, Chord, Mode, Scale, Error, Tuning, FretPosition, Fretboard};
+
+#[test]
+fn fretboard_tunings() {
+    assert_eq!(Tuning::standard().to_string(), "E2 A2 D3 G3 B3 E4");
+    assert_eq!(Tuning::drop_d().to_string(), "D2 A2 D3 G3 B3 E4");
+    assert_eq!(Tuning::dadgad().to_string(), "D2 A2 D3 G3 A3 D4");
+    assert_eq!(Tuning::open_g().to_string(), "D2 G2 D3 G3 B3 D4");
+    assert_eq!(Tuning::seven_string().get_strings().len(), 7);
+    assert_eq!(Tuning::bass().to_string(), "E1 A1 D2 G2");
+    assert_eq!(Tuning::ukulele().to_string(), "G4 C4 E4 A4");
+}
+
+#[test]
+fn fretboard_notes() {
+    let fretboard = Fretboard::new(Tuning::standard(), 22);
+    assert_eq!(fretboard.get_note(&FretPosition::new(0, 5)).unwrap(), "A2".parse::<MIDINote>().unwrap());
+    assert_eq!(fretboard.get_note(&FretPosition::new(5, 12)).unwrap(), "E5".parse::<MIDINote>().unwrap());
+    assert_eq!(fretboard.get_note(&FretPosition::new(6, 0)), Err(Error::InvalidFretPosition { string: 6, fret: 0 }));
+    assert!(fretboard.get_note(&FretPosition::new(0, 23)).is_err());
+
+    let positions = fretboard.get_positions(&"E4".parse().unwrap());
+    assert_eq!(positions, vec![
+        FretPosition::new(1, 19), FretPosition::new(2, 14), FretPosition::new(3, 9), FretPosition::new(4, 5), FretPosition::new(5, 0)
+    ]);
+    assert!(fretboard.get_positions(&"D2".parse().unwrap()).is_empty());
+
+    // Positions are the same on a drop D tuning except on the lowest string
+    let drop_d = Fretboard::new(Tuning::drop_d(), 22);
+    assert_eq!(drop_d.get_positions(&"D2".parse().unwrap()), vec![FretPosition::new(0, 0)]);
+}
+
+#[test]
+fn fretboard_find() {
+    let fretboard = Fretboard::new(Tuning::standard(), 22);
+    let positions = fretboard.find_note(&Note::try_from("Bb").unwrap(), 0, 4);
+    let names : Vec<String> = positions.iter().map(|(position, note)| format!("{} {}", position, note)).collect();
+    assert_eq!(names, vec!["1:1 Bb2", "3:3 Bb3"]);
+
+    let chord = Chord::try_from("C").unwrap();
+    let positions = fretboard.find_chord(&chord, 0, 3);
+    assert!(positions.iter().all(|(_, note)| chord.get_notes().contains(&note.get_note())));
+    assert!(positions.contains(&(FretPosition::new(1, 3), "C3".parse().unwrap())));
+    assert!(positions.contains(&(FretPosition::new(0, 0), "E2".parse().unwrap())));
+
+    let mode = Mode::new(Note::try_from("A").unwrap(), Scale::Major, 5);
+    let positions = fretboard.find_mode(&mode, 5, 8);
+    assert_eq!(positions.iter().filter(|(position, _)| position.get_string() == 0).count(), 3);
+
+    let ukulele = Fretboard::new(Tuning::ukulele(), 12);
+    let positions = ukulele.find_note(&Note::try_from("C").unwrap(), 0, 0);
+    assert_eq!(positions, vec![(FretPosition::new(1, 0), "C4".parse().unwrap())]);
+}
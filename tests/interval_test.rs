@@ -1,6 +1,7 @@
 extern crate musicmaster;
 
-use musicmaster::{Interval, Note, NoteLetter, NoteAccidental};
+use std::collections::HashMap;
+use musicmaster::{Interval, IntervalQuality, MIDINote, Note, NoteLetter, NoteAccidental};
 
 #[test]
 fn interval_string() {
@@ -16,4 +17,120 @@ fn interval_from_notes() {
     let fnote = Note::new(NoteLetter::F, NoteAccidental::Natural);
     assert_eq!(Interval::from_notes(&cnote, &fnote).get_value(), 5);
     assert_eq!(Interval::from_notes(&fnote, &cnote).get_value(), 7);
-}
\ No newline at end of file
+}
+
+#[test]
+fn interval_spelled_from_notes() {
+    let cnote = Note::new(NoteLetter::C, NoteAccidental::Natural);
+    assert_eq!(Interval::from_notes(&cnote, &Note::new(NoteLetter::D, NoteAccidental::Sharp)).to_string(), String::from("A2"));
+    assert_eq!(Interval::from_notes(&cnote, &Note::new(NoteLetter::E, NoteAccidental::Flat)).to_string(), String::from("m3"));
+    assert_eq!(Interval::from_notes(&cnote, &Note::new(NoteLetter::F, NoteAccidental::Sharp)).to_string(), String::from("A4"));
+    assert_eq!(Interval::from_notes(&cnote, &Note::new(NoteLetter::G, NoteAccidental::Flat)).to_string(), String::from("d5"));
+    assert_eq!(Interval::from_notes(&cnote, &Note::new(NoteLetter::G, NoteAccidental::DoubleSharp)).to_string(), String::from("AA5"));
+    assert_eq!(Interval::from_notes(&Note::new(NoteLetter::G, NoteAccidental::Sharp), &Note::new(NoteLetter::F, NoteAccidental::Natural)).to_string(), String::from("d7"));
+    assert_eq!(Interval::from_notes(&cnote, &Note::new(NoteLetter::F, NoteAccidental::Flat)).to_string(), String::from("d4"));
+}
+
+#[test]
+fn interval_from_name() {
+    let interval = Interval::from_name("A4").unwrap();
+    assert_eq!(interval.get_value(), 6);
+    assert_eq!(interval.get_steps(), 3);
+    assert_eq!(interval.get_quality(), IntervalQuality::Augmented(1));
+    assert_eq!(Interval::from_name("d7").unwrap().get_value(), 9);
+    assert_eq!(Interval::from_name("M7").unwrap().get_value(), 11);
+    assert!(Interval::from_name("P3").is_err());
+    assert!(Interval::from_name("M5").is_err());
+    assert_ne!(Interval::from_name("A2").unwrap(), Interval::from_name("m3").unwrap());
+}
+
+#[test]
+fn interval_apply() {
+    let gsharp = Note::new(NoteLetter::G, NoteAccidental::Sharp);
+    assert_eq!(Interval::from_name("M7").unwrap().apply(&gsharp).to_string(), String::from("F##"));
+    let eflat = Note::new(NoteLetter::E, NoteAccidental::Flat);
+    assert_eq!(Interval::from_name("m3").unwrap().apply(&eflat).to_string(), String::from("Gb"));
+    assert_eq!(Interval::from_name("A2").unwrap().apply(&eflat).to_string(), String::from("F#"));
+    assert_eq!(Interval::from_name("d4").unwrap().apply(&eflat).to_string(), String::from("Abb"));
+}
+
+#[test]
+fn interval_compound() {
+    assert_eq!(Interval::new(14).to_string(), String::from("M9"));
+    assert_eq!(Interval::new(17).to_string(), String::from("P11"));
+    assert_eq!(Interval::new(21).to_string(), String::from("M13"));
+    assert_eq!(Interval::new(12).to_string(), String::from("P8"));
+    assert_eq!(Interval::new(24).to_string(), String::from("P15"));
+    assert_eq!(Interval::new(28).to_string(), String::from("M17"));
+    assert!(Interval::new(14).is_compound());
+    assert!(!Interval::new(12).is_compound());
+    assert_eq!(Interval::from_name("m9").unwrap().get_value(), 13);
+    assert_eq!(Interval::from_name("A11").unwrap().get_value(), 18);
+}
+
+#[test]
+fn interval_descending() {
+    let interval = Interval::new(-7);
+    assert!(interval.is_descending());
+    assert_eq!(interval.to_string(), String::from("-P5"));
+    assert_eq!(Interval::from_name("-M3").unwrap().get_value(), -4);
+    let cnote = Note::new(NoteLetter::C, NoteAccidental::Natural);
+    assert_eq!(Interval::from_name("-P5").unwrap().apply(&cnote).to_string(), String::from("F"));
+    assert_eq!(Interval::from_name("-m3").unwrap().apply(&cnote).to_string(), String::from("A"));
+}
+
+#[test]
+fn interval_from_midi_notes() {
+    let c4 = MIDINote::new(NoteLetter::C, NoteAccidental::Natural, 4).unwrap();
+    let d5 = MIDINote::new(NoteLetter::D, NoteAccidental::Natural, 5).unwrap();
+    let f3 = MIDINote::new(NoteLetter::F, NoteAccidental::Natural, 3).unwrap();
+    assert_eq!(Interval::from_midi_notes(&c4, &d5).to_string(), String::from("M9"));
+    assert_eq!(Interval::from_midi_notes(&d5, &c4).to_string(), String::from("-M9"));
+    assert_eq!(Interval::from_midi_notes(&c4, &f3).to_string(), String::from("-P5"));
+    assert_eq!(Interval::from_midi_notes(&f3, &d5).get_value(), 21);
+    let bsharp3 = MIDINote::new(NoteLetter::B, NoteAccidental::Sharp, 3).unwrap();
+    assert_eq!(Interval::from_midi_notes(&bsharp3, &c4).to_string(), String::from("d2"));
+}
+
+#[test]
+fn interval_arithmetic() {
+    let major_third = Interval::from_name("M3").unwrap();
+    let minor_third = Interval::from_name("m3").unwrap();
+    assert_eq!((major_third + minor_third).to_string(), String::from("P5"));
+    assert_eq!((Interval::from_name("P5").unwrap() - major_third), minor_third);
+    assert_eq!((-major_third).to_string(), String::from("-M3"));
+    assert_eq!((Interval::from_name("P8").unwrap() + Interval::from_name("M2").unwrap()).to_string(), String::from("M9"));
+
+    let cnote = Note::new(NoteLetter::C, NoteAccidental::Natural);
+    assert_eq!((cnote + Interval::from_name("m7").unwrap()).to_string(), String::from("Bb"));
+    assert_eq!((cnote - major_third).to_string(), String::from("Ab"));
+    let b3 = MIDINote::new(NoteLetter::B, NoteAccidental::Natural, 3).unwrap();
+    assert_eq!((b3 + minor_third).unwrap().to_string(), String::from("D4"));
+    assert_eq!((b3 + Interval::from_name("A1").unwrap()).unwrap().to_string(), String::from("B#3"));
+    assert_eq!((b3 + Interval::from_name("M9").unwrap()).unwrap().to_string(), String::from("C#5"));
+    assert_eq!((b3 - Interval::from_name("P5").unwrap()).unwrap().to_string(), String::from("E3"));
+    assert!((MIDINote::new(NoteLetter::C, NoteAccidental::Natural, -1).unwrap() - Interval::from_name("m2").unwrap()).is_err());
+}
+
+#[test]
+fn interval_invert() {
+    assert_eq!(Interval::from_name("M3").unwrap().invert().to_string(), String::from("m6"));
+    assert_eq!(Interval::from_name("A4").unwrap().invert().to_string(), String::from("d5"));
+    assert_eq!(Interval::from_name("P1").unwrap().invert().to_string(), String::from("P8"));
+    assert_eq!(Interval::from_name("M9").unwrap().invert().to_string(), String::from("m7"));
+    assert_eq!(Interval::from_name("M9").unwrap().simple().to_string(), String::from("M2"));
+    assert_eq!(Interval::from_name("-M10").unwrap().simple().to_string(), String::from("-M3"));
+    assert_eq!(Interval::from_name("P15").unwrap().simple().to_string(), String::from("P8"));
+}
+
+#[test]
+fn interval_ordering() {
+    let mut intervals : Vec<Interval> = ["P5","m3","A2","M2","P1"].iter().map(|s| Interval::from_name(s).unwrap()).collect();
+    intervals.sort();
+    let names : Vec<String> = intervals.iter().map(|i| i.to_string()).collect();
+    assert_eq!(names, vec!["P1","M2","A2","m3","P5"]);
+    let mut counts : HashMap<Interval, u32> = HashMap::new();
+    *counts.entry(Interval::from_name("m3").unwrap()).or_insert(0) += 1;
+    *counts.entry(Interval::new(3)).or_insert(0) += 1;
+    assert_eq!(counts[&Interval::from_name("m3").unwrap()], 2);
+}
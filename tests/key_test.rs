@@ -0,0 +1,53 @@
+extern crate musicmaster;
+
+use std::convert::TryFrom;
+use musicmaster::{Key, KeyQuality, Mode, Note, Scale, Error};
+
+fn names(notes: Vec<Note>) -> Vec<String> {
+    notes.iter().map(|note| note.to_string()).collect()
+}
+
+#[test]
+fn key_signature() {
+    let key = Key::new(Note::try_from("E").unwrap(), KeyQuality::Major);
+    assert_eq!(key.get_signature(), 4);
+    assert_eq!(names(key.get_accidentals()), vec!["F#","C#","G#","D#"]);
+    let key = Key::new(Note::try_from("C").unwrap(), KeyQuality::Minor);
+    assert_eq!(key.get_signature(), -3);
+    assert_eq!(names(key.get_accidentals()), vec!["Bb","Eb","Ab"]);
+    let key = Key::new(Note::try_from("G#").unwrap(), KeyQuality::Major);
+    assert_eq!(key.get_signature(), 8);
+    assert_eq!(names(key.get_notes()), vec!["G#","A#","B#","C#","D#","E#","F##"]);
+}
+
+#[test]
+fn key_relative_parallel() {
+    let key = Key::new(Note::try_from("Eb").unwrap(), KeyQuality::Major);
+    assert_eq!(key.relative().to_string(), String::from("C minor"));
+    assert_eq!(key.relative().relative().to_string(), String::from("Eb major"));
+    assert_eq!(key.parallel().to_string(), String::from("Eb minor"));
+    assert_eq!(key.parallel().get_signature(), -6);
+}
+
+#[test]
+fn key_from_mode() {
+    let dorian = Mode::new(Note::try_from("D").unwrap(), Scale::Major, 1);
+    assert_eq!(Key::from_mode(&dorian).unwrap().to_string(), String::from("C major"));
+    let aeolian = Mode::new(Note::try_from("F#").unwrap(), Scale::Major, 5);
+    assert_eq!(Key::from_mode(&aeolian).unwrap().get_signature(), 3);
+    let whole_tone = Mode::new(Note::try_from("C").unwrap(), Scale::WholeTone, 0);
+    assert!(matches!(Key::from_mode(&whole_tone), Err(Error::NoKeySignature(_))));
+}
+
+#[test]
+fn key_spell() {
+    let key = Key::new(Note::try_from("F").unwrap(), KeyQuality::Major);
+    assert_eq!(key.spell(&Note::try_from("A#").unwrap()).to_string(), String::from("Bb"));
+    assert_eq!(key.spell(&Note::try_from("D#").unwrap()).to_string(), String::from("Eb"));
+    let key = Key::new(Note::try_from("D").unwrap(), KeyQuality::Major);
+    assert_eq!(key.spell(&Note::try_from("Gb").unwrap()).to_string(), String::from("F#"));
+    assert_eq!(key.spell(&Note::try_from("Ab").unwrap()).to_string(), String::from("G#"));
+    let key = Key::new(Note::try_from("D").unwrap(), KeyQuality::Minor);
+    assert_eq!(key.spell(&Note::try_from("Db").unwrap()).to_string(), String::from("C#"));
+    assert_eq!(key.spell(&Note::try_from("A#").unwrap()).to_string(), String::from("Bb"));
+}
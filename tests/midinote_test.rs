@@ -0,0 +1,66 @@
+extern crate musicmaster;
+
+use musicmaster::{Error, MIDINote, Note, NoteLetter, NoteAccidental, ParseNoteError};
+
+#[test]
+fn midinote_index() {
+    assert_eq!(MIDINote::new(NoteLetter::C, NoteAccidental::Natural, 4).unwrap().get_index(), 60);
+    assert_eq!(MIDINote::new(NoteLetter::B, NoteAccidental::Sharp, 3).unwrap().get_index(), 60);
+    assert_eq!(MIDINote::new(NoteLetter::C, NoteAccidental::Flat, 4).unwrap().get_index(), 59);
+    assert_eq!(MIDINote::new(NoteLetter::D, NoteAccidental::DoubleFlat, 4).unwrap().get_index(), 60);
+
+    // Notes below C-1 have no index
+    assert_eq!(MIDINote::new(NoteLetter::D, NoteAccidental::DoubleFlat, -1).unwrap().get_index(), 0);
+    assert_eq!(MIDINote::new(NoteLetter::C, NoteAccidental::Natural, -2), Err(Error::MidiNumberOutOfRange(-12)));
+    assert_eq!(MIDINote::new(NoteLetter::C, NoteAccidental::Flat, -1), Err(Error::MidiNumberOutOfRange(-1)));
+    assert!(MIDINote::new(NoteLetter::C, NoteAccidental::Natural, -1).unwrap().previous().is_err());
+}
+
+#[test]
+fn midinote_next_previous() {
+    let mut midi_note = MIDINote::new(NoteLetter::B, NoteAccidental::DoubleSharp, 3).unwrap();
+    for idx in 62..74 {
+        assert_eq!(midi_note.next().get_index(), idx);
+        midi_note = midi_note.next();
+    }
+    midi_note = MIDINote::new(NoteLetter::C, NoteAccidental::DoubleFlat, 5).unwrap();
+    for idx in (58..70).rev() {
+        assert_eq!(midi_note.previous().unwrap().get_index(), idx);
+        midi_note = midi_note.previous().unwrap();
+    }
+    for letter in musicmaster::all_note_letters() {
+        for accidental in musicmaster::all_note_accidentals() {
+            let midi_note = MIDINote::new(letter, accidental, 4).unwrap();
+            assert_eq!(midi_note.next().get_index(), midi_note.get_index() + 1);
+            assert_eq!(midi_note.previous().unwrap().get_index(), midi_note.get_index() - 1);
+        }
+    }
+}
+
+#[test]
+fn midinote_parse() {
+    let note : MIDINote = "C#4".parse().unwrap();
+    assert_eq!(note.get_index(), 61);
+    let note : MIDINote = "Bb-1".parse().unwrap();
+    assert_eq!(note.get_octave(), -1);
+    assert_eq!(note.get_note(), Note::new(NoteLetter::B, NoteAccidental::Flat));
+    assert_eq!("g♯3".parse::<MIDINote>().unwrap().to_string(), String::from("G#3"));
+    assert_eq!("C".parse::<MIDINote>(), Err(ParseNoteError::InvalidOctave(String::new())));
+    assert_eq!("C4.5".parse::<MIDINote>(), Err(ParseNoteError::InvalidOctave(String::from("4.5"))));
+
+    // Notes below C-1 are rejected
+    assert_eq!("C-1".parse::<MIDINote>().unwrap().get_index(), 0);
+    assert_eq!("Dbb-1".parse::<MIDINote>().unwrap().get_index(), 0);
+    assert_eq!("Cb-1".parse::<MIDINote>(), Err(ParseNoteError::OutOfRange(String::from("Cb-1"))));
+    assert_eq!("B-2".parse::<MIDINote>(), Err(ParseNoteError::OutOfRange(String::from("B-2"))));
+    assert_eq!(ParseNoteError::OutOfRange(String::from("Cb-1")).to_string(), "'Cb-1' is below C-1");
+}
+
+#[test]
+fn midinote_midi_number() {
+    assert_eq!(MIDINote::from_midi_number(60).unwrap().to_string(), String::from("C4"));
+    assert_eq!(MIDINote::from_midi_number(0).unwrap().to_string(), String::from("C-1"));
+    assert_eq!(MIDINote::from_midi_number(128), Err(Error::MidiNumberOutOfRange(128)));
+    assert_eq!(MIDINote::new(NoteLetter::A, NoteAccidental::Natural, 4).unwrap().get_midi_number(), Ok(69));
+    assert_eq!(MIDINote::new(NoteLetter::A, NoteAccidental::Natural, 9).unwrap().get_midi_number(), Err(Error::MidiNumberOutOfRange(129)));
+}
@@ -1,6 +1,6 @@
 extern crate musicmaster;
 
-use musicmaster::{Note, NoteLetter, NoteAccidental, Scale, Chord, Mode, ChordQuality, ChordPosition};
+use musicmaster::{Note, NoteLetter, NoteAccidental, Scale, Chord, Mode, ChordQuality, ChordPosition, Error};
 use musicmaster::{all_note_letters, all_note_accidentals};
 use std::convert::TryFrom;
 
@@ -14,7 +14,7 @@ fn scale_from() {
 fn mode_major() {
     let root_note = Note::new( NoteLetter::A, NoteAccidental::Natural);
     let mode = Mode::new(root_note, Scale::Major, 5);
-    let triads : Vec<Chord> = mode.get_chords(true, true).into_iter()
+    let triads : Vec<Chord> = mode.get_chords(true, 4).into_iter()
         .map(|opt| opt.unwrap())
         .collect();
 
@@ -26,13 +26,13 @@ fn mode_major() {
     assert_eq!(triads[5].to_string(), String::from("FMaj7"));
     assert_eq!(triads[6].to_string(), String::from("G7"));
 
-    assert_eq!(triads[0].as_numeral(&root_note, 0), String::from("I"));
-    assert_eq!(triads[1].as_numeral(&root_note, 1), String::from("II"));
-    assert_eq!(triads[2].as_numeral(&root_note, 2), String::from("bIII"));
-    assert_eq!(triads[3].as_numeral(&root_note, 3), String::from("IV"));
-    assert_eq!(triads[4].as_numeral(&root_note, 4), String::from("V"));
-    assert_eq!(triads[5].as_numeral(&root_note, 5), String::from("bVI"));
-    assert_eq!(triads[6].as_numeral(&root_note, 6), String::from("bVII"));
+    assert_eq!(triads[0].as_numeral(&root_note, 0).unwrap(), String::from("I"));
+    assert_eq!(triads[1].as_numeral(&root_note, 1).unwrap(), String::from("II"));
+    assert_eq!(triads[2].as_numeral(&root_note, 2).unwrap(), String::from("bIII"));
+    assert_eq!(triads[3].as_numeral(&root_note, 3).unwrap(), String::from("IV"));
+    assert_eq!(triads[4].as_numeral(&root_note, 4).unwrap(), String::from("V"));
+    assert_eq!(triads[5].as_numeral(&root_note, 5).unwrap(), String::from("bVI"));
+    assert_eq!(triads[6].as_numeral(&root_note, 6).unwrap(), String::from("bVII"));
 }
 
 #[test]
@@ -69,6 +69,9 @@ fn mode_chord_contain() {
 
     let fminor7 = Chord::new( Note::new( NoteLetter::F, NoteAccidental::Natural), ChordQuality::MinorSeventh, ChordPosition::Root );
     assert_eq!(phrygian_mode.chord_numeral(&fminor7).unwrap(), String::from("IV"));
+
+    let dmajor = Chord::new( Note::new( NoteLetter::D, NoteAccidental::Natural), ChordQuality::Major, ChordPosition::Root );
+    assert_eq!(phrygian_mode.chord_numeral(&dmajor), Err(Error::ChordNotInMode { chord: dmajor.to_string(), mode: phrygian_mode.to_string() }));
 }
 
 #[test]
@@ -85,4 +88,38 @@ fn all_major_modes() {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn mode_double_sharp_spelling() {
+    let root_note = Note::new( NoteLetter::G, NoteAccidental::Sharp );
+    let harmonic_minor = Mode::new( root_note, Scale::HarmonicMinor, 0 );
+    let notes : Vec<String> = harmonic_minor.get_notes(true).iter().map(|n| n.to_string()).collect();
+    assert_eq!(notes, vec!["G#","A#","B","C#","D#","E","F##"]);
+}
+
+#[test]
+fn scale_unknown_name() {
+    let err = Scale::try_from("Pentatonic").unwrap_err();
+    assert_eq!(err, Error::UnknownName { kind: "Scale", name: String::from("Pentatonic") });
+    assert_eq!(err.to_string(), String::from("No Scale found by the name 'Pentatonic'"));
+}
+
+#[test]
+fn mode_extended_chords() {
+    let mode = Mode::new( Note::try_from("C").unwrap(), Scale::Major, 0 );
+    let names : Vec<String> = mode.get_chords(true, 3).into_iter().map(|opt| opt.unwrap().to_string()).collect();
+    assert_eq!(names, vec!["C","Dm","Em","F","G","Am","Bº"]);
+    let ninths = mode.get_chords(true, 5);
+    assert_eq!(ninths[0].as_ref().unwrap().to_string(), "CMaj9");
+    assert_eq!(ninths[1].as_ref().unwrap().to_string(), "Dm9");
+    assert_eq!(ninths[4].as_ref().unwrap().to_string(), "G9");
+    assert!(ninths[2].is_none());
+    let thirteenths = mode.get_chords(true, 7);
+    assert_eq!(thirteenths[0].as_ref().unwrap().to_string(), "CMaj13");
+    assert_eq!(thirteenths[1].as_ref().unwrap().to_string(), "Dm13");
+    assert_eq!(thirteenths[4].as_ref().unwrap().to_string(), "G13");
+    // A whole tone scale repeats its notes before a seventh can be stacked
+    let mode = Mode::new( Note::try_from("C").unwrap(), Scale::WholeTone, 0 );
+    assert_eq!(mode.get_chords(true, 7)[0].as_ref().unwrap().to_string(), "C+");
+}
@@ -1,6 +1,6 @@
 extern crate musicmaster;
 
-use musicmaster::{Note, NoteAccidental, NoteLetter};
+use musicmaster::{Error, Note, NoteAccidental, NoteLetter, ParseNoteError};
 use std::convert::TryFrom;
 
 
@@ -64,8 +64,43 @@ fn note_index() {
 #[test]
 fn note_numeral() {
     let root_note = Note::new(NoteLetter::C, NoteAccidental::Natural);
-    assert_eq!(Note::new(NoteLetter::C, NoteAccidental::Sharp).as_numeral(&root_note, 1), String::from("2b"));
-    assert_eq!(Note::new(NoteLetter::F, NoteAccidental::Flat).as_numeral(&root_note, 2), String::from("3"));
-    assert_eq!(Note::new(NoteLetter::E, NoteAccidental::Natural).as_numeral(&root_note, 2), String::from("3"));
-    assert_eq!(Note::new(NoteLetter::G, NoteAccidental::Flat).as_numeral(&root_note, 4), String::from("5b"));
-}
\ No newline at end of file
+    assert_eq!(Note::new(NoteLetter::C, NoteAccidental::Sharp).as_numeral(&root_note, 1).unwrap(), String::from("2b"));
+    assert_eq!(Note::new(NoteLetter::F, NoteAccidental::Flat).as_numeral(&root_note, 2).unwrap(), String::from("3"));
+    assert_eq!(Note::new(NoteLetter::E, NoteAccidental::Natural).as_numeral(&root_note, 2).unwrap(), String::from("3"));
+    assert_eq!(Note::new(NoteLetter::G, NoteAccidental::Flat).as_numeral(&root_note, 4).unwrap(), String::from("5b"));
+}
+
+#[test]
+fn note_double_accidentals() {
+    let note = Note::try_from("F##").unwrap();
+    assert_eq!(note.get_accidental(), NoteAccidental::DoubleSharp);
+    assert_eq!(note.get_index(), 7);
+    assert_eq!(note.to_string(), String::from("F##"));
+    assert_eq!(Note::new(NoteLetter::C, NoteAccidental::DoubleFlat).get_index(), 10);
+    assert_eq!(Note::new(NoteLetter::B, NoteAccidental::DoubleSharp).to_string(), String::from("B##"));
+    let equivalents : Vec<String> = Note::try_from("C").unwrap().equivalents().iter().map(|n| n.to_string()).collect();
+    assert!(equivalents.contains(&String::from("B#")));
+    assert!(equivalents.contains(&String::from("Dbb")));
+}
+
+#[test]
+fn note_parse_any_spelling() {
+    for (name, expected) in [("Cb","Cb"),("E#","E#"),("B#","B#"),("Fb","Fb"),("eb","Eb"),("E♭","Eb"),("F##","F##"),("Fx","F##"),("f𝄪","F##"),("B♭♭","Bbb"),("bb","Bb"),("G♯","G#"),("A♮","A")].iter() {
+        let note : Note = name.parse().unwrap();
+        assert_eq!(note.to_string(), expected.to_string());
+    }
+    assert_eq!("H".parse::<Note>(), Err(ParseNoteError::InvalidLetter('H')));
+    assert_eq!("".parse::<Note>(), Err(ParseNoteError::Empty));
+    assert_eq!("C#?".parse::<Note>(), Err(ParseNoteError::InvalidAccidental('?')));
+    assert_eq!("C###".parse::<Note>(), Err(ParseNoteError::UnsupportedAccidental(3)));
+    assert_eq!(Note::try_from("H"), Err(Error::ParseNote(ParseNoteError::InvalidLetter('H'))));
+}
+
+#[test]
+fn note_respell() {
+    let note = Note::try_from("E").unwrap();
+    assert_eq!(note.respell(NoteLetter::F).unwrap().to_string(), String::from("Fb"));
+    assert_eq!(note.respell(NoteLetter::D).unwrap().to_string(), String::from("D##"));
+    assert_eq!(note.respell(NoteLetter::G), Err(Error::UnspellableNote { letter: NoteLetter::G, offset: -3 }));
+    assert!(NoteLetter::try_from("H").is_err());
+}
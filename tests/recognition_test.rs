@@ -0,0 +1,101 @@
+extern crate musicmaster;
+
+mod common;
+
+use std::convert::TryFrom;
+use musicmaster::{Note, Interval, ChordQuality, ChordPosition, recognise_chords, recognise_voicing};
+use common::midi_notes;
+
+fn notes(names: &[&str]) -> Vec<Note> {
+    names.iter().map(|s| Note::try_from(*s).unwrap()).collect()
+}
+
+#[test]
+fn recognise_omitted_fifth() {
+    let matches = recognise_chords(&notes(&["C","E","Bb"]));
+    assert_eq!(matches[0].get_chord().to_string(), "C7(no5)");
+    assert_eq!(matches[0].get_missing_tones(), vec![Interval::from_name("P5").unwrap()]);
+    assert!(matches[0].get_extra_notes().is_empty());
+    assert!(matches.windows(2).all(|w| w[0].get_score() >= w[1].get_score()));
+}
+
+#[test]
+fn recognise_complete_chords() {
+    let matches = recognise_chords(&notes(&["C","G","E"]));
+    assert_eq!(matches[0].get_chord().to_string(), "C");
+    assert!(matches[0].get_missing_tones().is_empty());
+
+    let matches = recognise_chords(&notes(&["A","C","E","G"]));
+    assert_eq!(matches[0].get_chord().to_string(), "Am7");
+    assert_eq!(matches[1].get_chord().to_string(), "C6/A");
+
+    let matches = recognise_chords(&notes(&["E","G","C","E"]));
+    let chord = matches[0].get_chord();
+    assert_eq!(chord.get_quality(), ChordQuality::Major);
+    assert_eq!(chord.get_position(), ChordPosition::FirstInversion);
+}
+
+#[test]
+fn recognise_rootless_and_extra_tones() {
+    // Rootless voicing of C9
+    let matches = recognise_chords(&notes(&["E","Bb","D","G"]));
+    assert_eq!(matches[0].get_chord().to_string(), "Em7(b5)");
+    let rootless = matches.iter().find(|m| m.get_chord().to_string() == "C9(no1)/E").unwrap();
+    assert_eq!(rootless.get_missing_tones(), vec![Interval::from_name("P1").unwrap()]);
+
+    // An added tone outside of any known shape is reported as extra
+    let matches = recognise_chords(&notes(&["C","E","G","C#"]));
+    assert_eq!(matches[0].get_chord().to_string(), "C");
+    assert_eq!(matches[0].get_extra_notes(), notes(&["C#"]));
+
+    // The bass note outside of the chord makes a slash chord
+    let matches = recognise_chords(&notes(&["D","F","A","C","Eb"]));
+    assert!(matches.iter().any(|m| m.get_chord().get_bass_note().is_some()));
+
+    assert!(recognise_chords(&[]).is_empty());
+}
+
+#[test]
+fn recognise_spelling_and_restatements() {
+    // A root among the notes keeps its spelling
+    let matches = recognise_chords(&notes(&["E","Bb","D"]));
+    assert_eq!(matches[0].get_chord().get_root().to_string(), "Bb");
+    assert!(matches.iter().all(|m| !m.get_chord().get_root().to_string().starts_with("A#")));
+
+    // Readings of the same notes from the same root are only given once
+    let matches = recognise_chords(&notes(&["C","E","Bb"]));
+    let names : Vec<String> = matches.iter().map(|m| m.get_chord().to_string()).collect();
+    assert_eq!(names[0], "C7(no5)");
+    assert!(!names.contains(&String::from("C7(b5)(no5)")));
+    assert!(!names.iter().any(|name| name.starts_with("C11")));
+    let names : Vec<String> = recognise_chords(&notes(&["C","E","G"])).iter().map(|m| m.get_chord().to_string()).collect();
+    assert!(!names.contains(&String::from("C7(no7)")));
+}
+
+#[test]
+fn recognise_voicings() {
+    // Close position
+    let matches = recognise_voicing(&midi_notes(&["C4","E4","G4"]));
+    assert_eq!(matches[0].get_chord().to_string(), "C");
+    assert!(matches[0].is_close_position());
+    assert_eq!(matches[0].get_span(), Interval::from_name("P5").unwrap());
+
+    // Open voicing given in any order, the lowest note setting the inversion
+    let matches = recognise_voicing(&midi_notes(&["C5","G4","E3","C4"]));
+    let best = &matches[0];
+    assert_eq!(best.get_chord().get_position(), ChordPosition::FirstInversion);
+    assert_eq!(best.get_chord().to_string(), "C/E");
+    assert_eq!(best.get_notes(), midi_notes(&["E3","C4","G4","C5"]));
+    let spacing : Vec<String> = best.get_spacing().iter().map(|i| i.to_string()).collect();
+    assert_eq!(spacing, vec!["m6","P5","P4"]);
+    assert!(!best.is_close_position());
+    assert_eq!(best.get_span(), Interval::from_name("m13").unwrap());
+
+    // Spread voicing of a seventh chord without its fifth
+    let matches = recognise_voicing(&midi_notes(&["B3","G2","F4"]));
+    assert_eq!(matches[0].get_chord().to_string(), "G7(no5)");
+    assert_eq!(matches[0].get_chord().get_quality(), ChordQuality::DominantSeventh);
+    assert_eq!(matches[0].to_string(), "G7(no5) (3.25) [M10,d5]");
+
+    assert!(recognise_voicing(&[]).is_empty());
+}
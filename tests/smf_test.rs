@@ -0,0 +1,178 @@
+extern crate musicmaster;
+
+mod common;
+
+use std::convert::TryFrom;
+use musicmaster::{Note, MIDINote, Key, KeyQuality, Mode, Scale, VoicingStyle, Error, NoteEvent, ProgramChange, Tempo, TimeSignature,
+    KeySignature, MetaEvent, Track, MidiFile, read_smf, write_smf, notes_from_voicings, voice_chord};
+use common::midi;
+
+#[test]
+fn smf_read_format_0() {
+    let file = read_smf(include_bytes!("fixtures/format0.mid")).unwrap();
+    assert_eq!(file.get_format(), 0);
+    assert_eq!(file.get_ticks_per_quarter(), 96);
+    let tracks = file.get_tracks();
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].get_name(), Some(String::from("Scale")));
+    assert_eq!(tracks[0].get_end_tick(), 144);
+
+    // Running status and note ons with a velocity of 0
+    assert_eq!(tracks[0].get_notes(), vec![
+        NoteEvent::new(0, 96, midi("C4"), 100, 0),
+        NoteEvent::new(0, 96, midi("E4"), 80, 0),
+        NoteEvent::new(96, 48, midi("G4"), 112, 0)
+    ]);
+    assert_eq!(tracks[0].get_program_changes(), vec![ProgramChange::new(0, 0, 24)]);
+
+    let time_signature = file.get_time_signatures()[0];
+    assert_eq!(time_signature.to_string(), "3/4");
+    assert_eq!(time_signature.get_clocks_per_click(), 24);
+    let key_signature = file.get_key_signatures()[0];
+    assert_eq!(key_signature.get_key(), Key::new(Note::try_from("D").unwrap(), KeyQuality::Minor));
+    assert_eq!(file.get_tempo_map()[0].get_bpm(), 120.0);
+
+    let meta_events = tracks[0].get_meta_events();
+    assert_eq!(meta_events.len(), 1);
+    assert_eq!((meta_events[0].get_tick(), meta_events[0].get_kind()), (144, 0x05));
+    assert_eq!(meta_events[0].get_text(), Some(String::from("la")));
+}
+
+#[test]
+fn smf_read_format_1() {
+    let file = read_smf(include_bytes!("fixtures/format1.mid")).unwrap();
+    assert_eq!(file.get_format(), 1);
+    let tracks = file.get_tracks();
+    assert_eq!(tracks.len(), 2);
+    assert_eq!(tracks[0].get_name(), Some(String::from("Conductor")));
+    assert!(tracks[0].get_notes().is_empty());
+
+    // Control changes, pitch bends and system exclusive messages are skipped and
+    // notes sounding at the end of the track last until then
+    assert_eq!(tracks[1].get_name(), Some(String::from("Bass")));
+    assert_eq!(tracks[1].get_notes(), vec![
+        NoteEvent::new(0, 3840, midi("E2"), 100, 1),
+        NoteEvent::new(3840, 480, midi("A2"), 80, 1)
+    ]);
+    assert_eq!(tracks[1].get_program_changes(), vec![ProgramChange::new(0, 1, 33)]);
+    assert_eq!(file.get_notes(), tracks[1].get_notes());
+
+    let tempos = file.get_tempo_map();
+    assert_eq!(tempos.iter().map(|tempo| (tempo.get_tick(), tempo.get_bpm())).collect::<Vec<(u64, f64)>>(),
+        vec![(0, 120.0), (1920, 60.0)]);
+    assert_eq!(file.tick_to_seconds(960), 1.0);
+    assert_eq!(file.tick_to_seconds(1920), 2.0);
+    assert_eq!(file.tick_to_seconds(3840), 6.0);
+    assert_eq!(file.get_key_signatures()[0].get_key(), Key::new(Note::try_from("D").unwrap(), KeyQuality::Major));
+}
+
+#[test]
+fn smf_read_errors() {
+    let bytes = include_bytes!("fixtures/format0.mid");
+    assert_eq!(read_smf(b"RIFF"), Err(Error::InvalidMidiFile(String::from("missing MThd header"))));
+    assert_eq!(read_smf(&bytes[..40]), Err(Error::InvalidMidiFile(String::from("unexpected end of data"))));
+    assert_eq!(read_smf(&bytes[..14]), Err(Error::InvalidMidiFile(String::from("expected 1 tracks but found 0"))));
+
+    let mut smpte = bytes.to_vec();
+    smpte[12] = 0xE7;
+    assert_eq!(read_smf(&smpte), Err(Error::InvalidMidiFile(String::from("SMPTE time division is not supported"))));
+    let mut no_division = bytes.to_vec();
+    no_division[13] = 0;
+    assert_eq!(read_smf(&no_division), Err(Error::InvalidMidiFile(String::from("0 ticks per quarter note is out of range"))));
+    let mut format_2 = bytes.to_vec();
+    format_2[9] = 2;
+    assert_eq!(read_smf(&format_2), Err(Error::InvalidMidiFile(String::from("format 2 is not supported"))));
+    let position = bytes.windows(3).position(|window| window == [0xFF, 0x59, 0x02]).unwrap();
+    let mut key_signature = bytes.to_vec();
+    key_signature[position + 3] = 8;
+    assert_eq!(read_smf(&key_signature), Err(Error::InvalidMidiFile(String::from("key signature of 8 is out of range"))));
+}
+
+#[test]
+fn smf_write_bytes() {
+    let notes = vec![ NoteEvent::new(0, 96, midi("C4"), 100, 0), NoteEvent::new(96, 96, midi("D4"), 100, 0) ];
+    let track = Track::new(Some("A"), notes)
+        .with_tempos(&[Tempo::from_bpm(0, 120.0)])
+        .with_time_signatures(&[TimeSignature::new(0, 6, 8)])
+        .with_key_signatures(&[KeySignature::new(0, Key::new(Note::try_from("Bb").unwrap(), KeyQuality::Major))])
+        .with_program_changes(&[ProgramChange::new(0, 0, 0)]);
+    let file = MidiFile::new(0, 96, vec![ track ]).unwrap();
+    assert_eq!(write_smf(&file).unwrap(), vec![
+        b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0, 96,
+        b'M', b'T', b'r', b'k', 0, 0, 0, 49,
+        0x00, 0xFF, 0x03, 0x01, b'A',
+        0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20,
+        0x00, 0xFF, 0x58, 0x04, 0x06, 0x03, 0x0C, 0x08,
+        0x00, 0xFF, 0x59, 0x02, 0xFE, 0x00,
+        0x00, 0xC0, 0x00,
+        0x00, 0x90, 0x3C, 0x64,
+        0x60, 0x80, 0x3C, 0x40,
+        0x00, 0x90, 0x3E, 0x64,
+        0x60, 0x80, 0x3E, 0x40,
+        0x00, 0xFF, 0x2F, 0x00
+    ]);
+}
+
+#[test]
+fn smf_write_round_trip() {
+    for bytes in [include_bytes!("fixtures/format0.mid").to_vec(), include_bytes!("fixtures/format1.mid").to_vec()].iter() {
+        let file = read_smf(bytes).unwrap();
+        assert_eq!(read_smf(&write_smf(&file).unwrap()).unwrap(), file);
+    }
+
+    // Chords of a mode, a conductor track holding the tempo map
+    let mode = Mode::new(Note::try_from("C").unwrap(), Scale::Major, 1);
+    let voicings : Vec<Vec<MIDINote>> = mode.get_chords(true, 4).into_iter()
+        .map(|chord| voice_chord(&chord.unwrap(), VoicingStyle::Close, &midi("C3"), &midi("C5"))[0].clone())
+        .collect();
+    let conductor = Track::new(Some("Conductor"), Vec::new())
+        .with_tempos(&[Tempo::from_bpm(0, 80.0)])
+        .with_time_signatures(&[TimeSignature::new(0, 4, 4)])
+        .with_key_signatures(&[KeySignature::new(0, Key::new(Note::try_from("C").unwrap(), KeyQuality::Major))])
+        .with_meta_events(&[MetaEvent::new(0, 0x06, b"Verse".to_vec())]);
+    let chords = Track::new(Some("Chords"), notes_from_voicings(&voicings, 480, 90, 2))
+        .with_program_changes(&[ProgramChange::new(0, 2, 0)])
+        .with_end_tick(4000);
+    let file = MidiFile::new(1, 480, vec![ conductor, chords ]).unwrap();
+    let read = read_smf(&write_smf(&file).unwrap()).unwrap();
+    assert_eq!(read, file);
+    assert_eq!(read.get_notes().len(), 7 * 4);
+    assert!(read.get_notes().iter().enumerate().all(|(idx, event)| event.get_tick() == 480 * (idx as u64 / 4)
+        && (event.get_duration(), event.get_velocity(), event.get_channel()) == (480, 90, 2)));
+    assert_eq!(read.get_tracks()[1].get_end_tick(), 4000);
+    assert_eq!(read.tick_to_seconds(480 * 7), 5.25);
+    assert_eq!(read.get_time_signatures()[0].get_clocks_per_click(), 24);
+}
+
+#[test]
+fn smf_write_errors() {
+    assert_eq!(MidiFile::new(0, 96, Vec::new()), Err(Error::InvalidMidiFile(String::from("format 0 file with 0 tracks"))));
+    assert_eq!(MidiFile::new(2, 96, Vec::new()), Err(Error::InvalidMidiFile(String::from("format 2 is not supported"))));
+    assert_eq!(MidiFile::new(1, 0, Vec::new()), Err(Error::InvalidMidiFile(String::from("0 ticks per quarter note is out of range"))));
+
+    let track = Track::new(None, vec![ NoteEvent::new(0, 96, midi("G#9"), 100, 0) ]);
+    assert_eq!(write_smf(&MidiFile::new(0, 96, vec![ track ]).unwrap()), Err(Error::MidiNumberOutOfRange(128)));
+
+    // Delta times are written in at most 4 bytes
+    let track = Track::new(None, vec![ NoteEvent::new(0x1000_0000, 96, midi("C4"), 100, 0) ]);
+    assert_eq!(write_smf(&MidiFile::new(0, 96, vec![ track ]).unwrap()),
+        Err(Error::InvalidMidiFile(String::from("delta time of 268435456 ticks is too large"))));
+    let track = Track::new(None, Vec::new()).with_end_tick(0x0FFF_FFFF);
+    assert!(write_smf(&MidiFile::new(0, 96, vec![ track ]).unwrap()).is_ok());
+
+    // Values that do not fit their event are not clamped
+    let write = |track: Track| write_smf(&MidiFile::new(0, 96, vec![ track ]).unwrap());
+    let error = |message: &str| Err(Error::InvalidMidiFile(String::from(message)));
+    assert_eq!(write(Track::new(None, vec![ NoteEvent::new(0, 96, midi("C4"), 100, 16) ])), error("channel 16 is out of range"));
+    assert_eq!(write(Track::new(None, vec![ NoteEvent::new(0, 96, midi("C4"), 0, 0) ])), error("velocity 0 is out of range"));
+    assert_eq!(write(Track::new(None, vec![ NoteEvent::new(0, 96, midi("C4"), 128, 0) ])), error("velocity 128 is out of range"));
+    let empty = Track::new(None, Vec::new());
+    assert_eq!(write(empty.with_program_changes(&[ProgramChange::new(0, 16, 0)])), error("channel 16 is out of range"));
+    assert_eq!(write(empty.with_program_changes(&[ProgramChange::new(0, 0, 128)])), error("program 128 is out of range"));
+    assert_eq!(write(empty.with_time_signatures(&[TimeSignature::new(0, 3, 3)])), error("time signature denominator 3 is not a power of 2"));
+    assert_eq!(write(empty.with_time_signatures(&[TimeSignature::new(0, 3, 0)])), error("time signature denominator 0 is not a power of 2"));
+    assert_eq!(write(empty.with_tempos(&[Tempo::new(0, 0x100_0000)])),
+        error("tempo of 16777216 microseconds per quarter note is out of range"));
+    let g_sharp_major = Key::new(Note::try_from("G#").unwrap(), KeyQuality::Major);
+    assert_eq!(write(empty.with_key_signatures(&[KeySignature::new(0, g_sharp_major)])), error("key signature of 8 is out of range"));
+}
@@ -0,0 +1,44 @@
+extern crate musicmaster;
+
+use std::convert::TryFrom;
+use musicmaster::{Chord, ChordPosition, ChordQuality, Interval, Key, KeyQuality, Mode, Note, Scale, SpellingPolicy};
+
+fn names(notes: Vec<Note>) -> Vec<String> {
+    notes.iter().map(|note| note.to_string()).collect()
+}
+
+#[test]
+fn spelling_policies() {
+    let note = Note::try_from("A#").unwrap();
+    assert_eq!(SpellingPolicy::PreferFlats.spell(&note).to_string(), String::from("Bb"));
+    assert_eq!(SpellingPolicy::PreferSharps.spell(&Note::try_from("Bb").unwrap()).to_string(), String::from("A#"));
+    assert_eq!(SpellingPolicy::PreferSharps.spell(&Note::try_from("Fb").unwrap()).to_string(), String::from("E"));
+    assert_eq!(SpellingPolicy::MinimiseAccidentals.spell(&Note::try_from("F##").unwrap()).to_string(), String::from("G"));
+    assert_eq!(SpellingPolicy::MinimiseAccidentals.spell(&note).to_string(), String::from("A#"));
+    let key = Key::new(Note::try_from("F").unwrap(), KeyQuality::Major);
+    assert_eq!(SpellingPolicy::KeySignature(key).spell(&note).to_string(), String::from("Bb"));
+}
+
+#[test]
+fn spelling_next_previous() {
+    let note = Note::try_from("A").unwrap();
+    assert_eq!(note.next_with(&SpellingPolicy::PreferFlats).to_string(), String::from("Bb"));
+    assert_eq!(note.previous_with(&SpellingPolicy::PreferSharps).to_string(), String::from("G#"));
+}
+
+#[test]
+fn spelling_interval_chord_mode() {
+    let cnote = Note::try_from("C").unwrap();
+    let augmented_sixth = Interval::from_name("A6").unwrap();
+    assert_eq!(augmented_sixth.apply(&cnote).to_string(), String::from("A#"));
+    assert_eq!(augmented_sixth.apply_with(&cnote, &SpellingPolicy::PreferFlats).to_string(), String::from("Bb"));
+
+    let chord = Chord::new(Note::try_from("Bb").unwrap(), ChordQuality::DominantSeventh, ChordPosition::Root);
+    assert_eq!(names(chord.get_notes()), vec!["Bb","D","F","Ab"]);
+    assert_eq!(names(chord.get_notes_with(&SpellingPolicy::PreferSharps)), vec!["A#","D","F","G#"]);
+
+    let mode = Mode::new(Note::try_from("G#").unwrap(), Scale::HarmonicMinor, 0);
+    assert_eq!(names(mode.get_notes_with(&SpellingPolicy::MinimiseAccidentals)), vec!["G#","A#","B","C#","D#","E","G"]);
+    let key = Key::new(Note::try_from("Ab").unwrap(), KeyQuality::Minor);
+    assert_eq!(names(mode.get_notes_with(&SpellingPolicy::KeySignature(key))), vec!["Ab","Bb","Cb","Db","Eb","Fb","G"]);
+}
@@ -0,0 +1,94 @@
+extern crate musicmaster;
+
+mod common;
+
+use musicmaster::{MIDINote, Tuning, Fretboard, FretPosition, Error, TabNote, parse_tab, write_tab, render_tab, render_melody_tab};
+use common::{fretboard, midi, midi_notes};
+
+#[test]
+fn tab_parse() {
+    let text = "
+Intro
+e|-----0-----|-----------|
+B|---1---1---|-----------|
+G|-0-------0-|-----------|
+D|-----------|-2h3---12--|
+A|-3---------|-----------|
+E|-----------|-----------|
+
+e|-------
+B|-------
+G|-------
+D|-------
+A|-------
+E|-0-----
+";
+    let notes = parse_tab(text, &fretboard()).unwrap();
+    let times : Vec<u32> = notes.iter().map(|tab_note| tab_note.get_time()).collect();
+    assert_eq!(times, vec![1, 1, 3, 5, 7, 9, 12, 14, 18, 23]);
+    let pitches : Vec<MIDINote> = notes.iter().map(|tab_note| tab_note.get_note()).collect();
+    assert_eq!(pitches, midi_notes(&["C3", "G3", "C4", "E4", "C4", "G3", "E3", "F3", "D4", "E2"]));
+    assert_eq!(notes[0].get_position(), FretPosition::new(1, 3));
+    assert_eq!(notes[8].get_position(), FretPosition::new(2, 12));
+
+    // Lines without names or bar lines
+    let notes = parse_tab("--5--\n--5--\n-----\n-----\n-----\n-----\n", &fretboard()).unwrap();
+    assert_eq!(notes.iter().map(|tab_note| tab_note.get_note()).collect::<Vec<MIDINote>>(), midi_notes(&["E4", "A4"]));
+}
+
+#[test]
+fn tab_parse_errors() {
+    assert_eq!(parse_tab("e|--0--\nB|--1--\n", &fretboard()),
+        Err(Error::InvalidTab(String::from("expected 6 strings but found 2 lines"))));
+    let ukulele = Fretboard::new(Tuning::ukulele(), 12);
+    assert_eq!(parse_tab("A|-15-\nE|----\nC|----\nG|----\n", &ukulele),
+        Err(Error::InvalidFretPosition { string: 3, fret: 15 }));
+    assert_eq!(parse_tab("no tablature here", &fretboard()), Ok(Vec::new()));
+}
+
+#[test]
+fn tab_write() {
+    let notes = vec![
+        TabNote::new(1, FretPosition::new(1, 3), midi("C3")),
+        TabNote::new(4, FretPosition::new(2, 12), midi("D4"))
+    ];
+    assert_eq!(write_tab(&fretboard(), &notes), "\
+e|-------|
+B|-------|
+G|-------|
+D|----12-|
+A|-3-----|
+E|-------|
+");
+    let parsed = parse_tab(&write_tab(&fretboard(), &notes), &fretboard()).unwrap();
+    assert_eq!(parsed, notes);
+}
+
+#[test]
+fn tab_render() {
+    let voicings = vec![ midi_notes(&["C3", "E3", "G3", "C4", "E4"]), Vec::new(), midi_notes(&["G2", "B2", "D3", "G3", "B3", "G4"]) ];
+    assert_eq!(render_tab(&fretboard(), &voicings).unwrap(), "\
+e|-0-----3-|
+B|-1-----0-|
+G|-0-----0-|
+D|-2-----0-|
+A|-3-----2-|
+E|-------3-|
+");
+    assert_eq!(render_melody_tab(&fretboard(), &midi_notes(&["E2", "G2", "A2", "C3", "D5"])).unwrap(), "\
+e|-------------10-|
+B|----------------|
+G|----------------|
+D|----------------|
+A|-------0--3-----|
+E|-0--3-----------|
+");
+    let ukulele = Fretboard::new(Tuning::ukulele(), 12);
+    assert_eq!(render_tab(&ukulele, &[midi_notes(&["G4", "C4", "E4", "C5"])]).unwrap(), "\
+A|-3-|
+E|-0-|
+C|-0-|
+G|-0-|
+");
+    assert_eq!(render_tab(&ukulele, &[midi_notes(&["C4", "C4"])]), Err(Error::UnplayableNotes(midi_notes(&["C4", "C4"]))));
+}
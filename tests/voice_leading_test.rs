@@ -0,0 +1,69 @@
+extern crate musicmaster;
+
+mod common;
+
+use std::convert::TryFrom;
+use musicmaster::{Chord, chord_voicings, voice_lead, voice_lead_progression, voice_leading_distance};
+use common::{midi, midi_notes, names};
+
+#[test]
+fn voice_lead_common_tones() {
+    // C to F holds C and moves the others by step
+    let from = midi_notes(&["C4","E4","G4"]);
+    let to = voice_lead(&from, &Chord::try_from("F").unwrap()).unwrap();
+    assert_eq!(names(&to), vec!["C4","F4","A4"]);
+    assert_eq!(voice_leading_distance(&from, &to), 3);
+
+    // Dm7 to G7 holds D and F and moves A and C down by step
+    let from = midi_notes(&["D4","F4","A4","C5"]);
+    let to = voice_lead(&from, &Chord::try_from("G7").unwrap()).unwrap();
+    assert_eq!(names(&to), vec!["D4","F4","G4","B4"]);
+    assert!(to.windows(2).all(|w| w[0] < w[1]));
+
+    assert!(voice_lead(&[], &Chord::try_from("C").unwrap()).is_none());
+}
+
+#[test]
+fn voice_lead_inversions() {
+    let from = midi_notes(&["C3","E4","G4","C5"]);
+    let to = voice_lead(&from, &Chord::try_from("G/B").unwrap()).unwrap();
+    assert_eq!(to[0].to_string(), "B2");
+    let to = voice_lead(&from, &Chord::try_from("F/G").unwrap()).unwrap();
+    assert_eq!(to[0].to_string(), "G2");
+    assert!(to[1..].iter().all(|note| note.get_note().to_string() != "G"));
+}
+
+#[test]
+fn voicings_with_fewer_voices() {
+    // Three voices for a seventh chord leave out the fifth
+    let voicings = chord_voicings(&Chord::try_from("G7").unwrap(), 3, &midi("C4"), &midi("C5"));
+    assert!(!voicings.is_empty());
+    for voicing in voicings.iter() {
+        let notes : Vec<String> = voicing.iter().map(|note| note.get_note().to_string()).collect();
+        assert!(notes.contains(&"B".to_string()) && notes.contains(&"F".to_string()) && notes.contains(&"G".to_string()));
+    }
+}
+
+#[test]
+fn voice_lead_whole_progression() {
+    let chords : Vec<Chord> = ["Dm7","G7","Cmaj7","A7"].iter().map(|s| Chord::try_from(*s).unwrap()).collect();
+    let (lowest, highest) = (midi("C3"), midi("C6"));
+    let voicings = voice_lead_progression(&chords, 4, &lowest, &highest).unwrap();
+    assert_eq!(voicings.len(), 4);
+    let total : u32 = voicings.windows(2).map(|w| voice_leading_distance(&w[0], &w[1])).sum();
+
+    // No greedy path from any starting voicing in the middle of the range does better,
+    // those paths staying within the range the progression was chosen from
+    for start in chord_voicings(&chords[0], 4, &midi("C4"), &midi("C5")) {
+        let mut current = start;
+        let mut greedy_total = 0;
+        for chord in chords[1..].iter() {
+            let next = voice_lead(&current, chord).unwrap();
+            assert!(next.iter().all(|note| *note >= lowest && *note <= highest));
+            greedy_total += voice_leading_distance(&current, &next);
+            current = next;
+        }
+        assert!(total <= greedy_total);
+    }
+    assert!(voice_lead_progression(&[], 4, &midi("C4"), &midi("C5")).is_none());
+}
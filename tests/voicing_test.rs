@@ -0,0 +1,82 @@
+extern crate musicmaster;
+
+mod common;
+
+use std::convert::TryFrom;
+use musicmaster::{Chord, VoicingStyle, all_voicing_styles, voice_chord};
+use common::{midi, names};
+
+#[test]
+fn voicing_close_and_open() {
+    let chord = Chord::try_from("C").unwrap();
+    let voicings = voice_chord(&chord, VoicingStyle::Close, &midi("C4"), &midi("C5"));
+    let voicings : Vec<Vec<String>> = voicings.iter().map(|v| names(v)).collect();
+    assert_eq!(voicings, vec![
+        vec!["C4","E4","G4"],
+        vec!["E4","G4","C5"]
+    ]);
+    let voicings = voice_chord(&chord, VoicingStyle::Open, &midi("C3"), &midi("C5"));
+    assert_eq!(names(&voicings[0]), vec!["C3","G3","E4"]);
+}
+
+#[test]
+fn voicing_inversions() {
+    let chord = Chord::try_from("C/E").unwrap();
+    let voicings = voice_chord(&chord, VoicingStyle::Close, &midi("C4"), &midi("C5"));
+    let voicings : Vec<Vec<String>> = voicings.iter().map(|v| names(v)).collect();
+    assert_eq!(voicings, vec![ vec!["E4","G4","C5"] ]);
+    for style in all_voicing_styles() {
+        for voicing in voice_chord(&chord, style, &midi("C2"), &midi("G5")) {
+            assert_eq!(voicing[0].get_note(), chord.get_lowest_note(), "{} {:?}", style, names(&voicing));
+        }
+    }
+    let voicings = voice_chord(&Chord::try_from("C7/G").unwrap(), VoicingStyle::Shell, &midi("C3"), &midi("C5"));
+    assert_eq!(names(&voicings[0]), vec!["G3","C4","E4","Bb4"]);
+}
+
+#[test]
+fn voicing_drops() {
+    let chord = Chord::try_from("Cmaj7").unwrap();
+    let low = midi("C3");
+    let high = midi("C5");
+    let drop2 = voice_chord(&chord, VoicingStyle::Drop2, &low, &high);
+    assert!(drop2.iter().any(|v| names(v) == vec!["G3","C4","E4","B4"]));
+    let drop3 = voice_chord(&chord, VoicingStyle::Drop3, &low, &high);
+    assert!(drop3.iter().any(|v| names(v) == vec!["E3","C4","G4","B4"]));
+    let drop24 = voice_chord(&chord, VoicingStyle::Drop24, &low, &high);
+    assert!(drop24.iter().any(|v| names(v) == vec!["C3","G3","E4","B4"]));
+    // Drop 3 needs four voices
+    assert!(voice_chord(&Chord::try_from("C").unwrap(), VoicingStyle::Drop3, &low, &high).is_empty());
+}
+
+#[test]
+fn voicing_jazz_styles() {
+    let low = midi("C2");
+    let high = midi("G5");
+    let chord = Chord::try_from("G7").unwrap();
+    let shells = voice_chord(&chord, VoicingStyle::Shell, &low, &high);
+    assert!(shells.iter().any(|v| names(v) == vec!["G2","B2","F3"]));
+    assert!(shells.iter().any(|v| names(v) == vec!["G2","F3","B3"]));
+    let rootless = voice_chord(&chord, VoicingStyle::RootlessA, &low, &high);
+    assert!(rootless.iter().any(|v| names(v) == vec!["B3","E4","F4","A4"]));
+    let rootless = voice_chord(&Chord::try_from("Dm7").unwrap(), VoicingStyle::RootlessB, &low, &high);
+    assert!(rootless.iter().any(|v| names(v) == vec!["C4","E4","F4","A4"]));
+    let spread = voice_chord(&Chord::try_from("Cmaj7").unwrap(), VoicingStyle::Spread, &low, &high);
+    assert_eq!(names(&spread[0]), vec!["C2","E3","G3","B3"]);
+    assert!(voice_chord(&Chord::try_from("Csus4").unwrap(), VoicingStyle::Shell, &low, &high).is_empty());
+}
+
+#[test]
+fn voicing_register() {
+    let low = midi("E3");
+    let high = midi("A4");
+    let chord = Chord::try_from("F/G").unwrap();
+    for style in all_voicing_styles() {
+        for voicing in voice_chord(&chord, style, &low, &high) {
+            assert!(voicing.iter().all(|note| *note >= low && *note <= high), "{} {:?}", style, names(&voicing));
+            assert!(voicing.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+    let close = voice_chord(&chord, VoicingStyle::Close, &low, &high);
+    assert_eq!(names(&close[0]), vec!["G3","A3","C4","F4"]);
+}